@@ -0,0 +1,125 @@
+use std::process::ExitCode;
+
+use crate::{
+    error::SolutionError,
+    solutions::{self, SOLUTIONS},
+    util::{
+        answers::Answers,
+        inputs::{InputFile, Inputs},
+        PartSelection, SolutionPart,
+    },
+};
+
+use super::{
+    run_all::{run_days, status_of},
+    table::Table,
+};
+
+/// Records `answer` as the accepted answer for `part` of day `problem`, or
+/// solves the day's input and records what it comes up with when no answer
+/// is given.
+pub fn record(
+    inputs: &Inputs,
+    problem: u8,
+    part: PartSelection,
+    answer: Option<String>,
+) -> Result<ExitCode, SolutionError> {
+    let answers_path = inputs.answers_path();
+    let mut answers = Answers::load(&answers_path)?;
+
+    match answer {
+        Some(answer) => {
+            let [part] = part.parts() else {
+                eprintln!("An answer can only be recorded for one part at a time.");
+                return Ok(ExitCode::FAILURE);
+            };
+            println!("Day {} part {}: {}", problem, part.number(), answer);
+            answers.record(problem, *part, answer);
+        }
+        None => {
+            let Some(solver) = solutions::find(problem) else {
+                return Err(SolutionError::UnknownProblem(problem));
+            };
+            let problem_data = inputs.load(problem, InputFile::Puzzle)?;
+            let solved = solver.solve_parts(&problem_data, part.parts())?;
+            for part_solved in solved.parts {
+                let answer = part_solved.answer?;
+                println!(
+                    "Day {} part {}: {}",
+                    problem,
+                    part_solved.part.number(),
+                    answer
+                );
+                answers.record(problem, part_solved.part, answer);
+            }
+        }
+    }
+
+    answers.save(&answers_path)?;
+    println!("Recorded in {}", answers_path.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Solves every day against its input and compares each answer with the
+/// recorded one. Fails if any answer changed or any day failed to solve.
+pub fn verify(inputs: &Inputs, jobs: usize) -> Result<ExitCode, SolutionError> {
+    let answers = Answers::load(&inputs.answers_path())?;
+    let results = run_days(SOLUTIONS, inputs, jobs);
+
+    let mut table = Table::new(&["Day", "Part", "Expected", "Answer", "Status"]);
+    let mut failed = false;
+    for (solver, result) in SOLUTIONS.iter().zip(results) {
+        let day = solver.day();
+        // Each part's answer, or the status to report when it has none.
+        let answered: Vec<(SolutionPart, Result<String, String>)> = match result {
+            Ok(solved) => solved
+                .parts
+                .into_iter()
+                .map(|part_solved| {
+                    let answer = part_solved.answer.map_err(|err| status_of(&err));
+                    (part_solved.part, answer)
+                })
+                .collect(),
+            Err(err) => PartSelection::Both
+                .parts()
+                .iter()
+                .map(|part| (*part, Err(status_of(&err))))
+                .collect(),
+        };
+
+        for (part, answer) in answered {
+            let expected = answers.get(day, part);
+            let (answer, status) = match answer {
+                Ok(answer) => {
+                    let status = match expected {
+                        Some(expected) if expected == answer => "ok",
+                        Some(_) => {
+                            failed = true;
+                            "mismatch"
+                        }
+                        None => "not recorded",
+                    };
+                    (answer, status.to_string())
+                }
+                Err(status) => {
+                    failed = true;
+                    (String::from("-"), status)
+                }
+            };
+            table.push(vec![
+                day.to_string(),
+                part.number().to_string(),
+                expected.unwrap_or("-").to_string(),
+                answer,
+                status,
+            ]);
+        }
+    }
+    println!("{}", table.render());
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
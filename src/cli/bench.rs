@@ -0,0 +1,416 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::SolutionError,
+    solutions::{self, Solver, SOLUTIONS},
+    util::{
+        inputs::{InputFile, Inputs},
+        PartSelection,
+    },
+};
+
+use super::table::Table;
+
+/// How many times to run each day, and how many runs to throw away first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+/// Every benchmarked day, as printed by `bench --json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BenchReport {
+    #[serde(flatten)]
+    pub options: BenchOptions,
+    pub days: Vec<DayBench>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayBench {
+    pub day: u8,
+    pub parse: Stats,
+    pub parts: Vec<PartBench>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PartBench {
+    pub part: u8,
+    pub solve: Stats,
+}
+
+/// Where to compare a run's timings against and where to save them.
+pub struct Baselines {
+    pub compare_with: Option<PathBuf>,
+    pub save_to: Option<PathBuf>,
+    /// How much slower than the baseline, in percent, a median may get
+    /// before it counts as a regression.
+    pub threshold: f64,
+}
+
+/// Summary of a set of timings, in nanoseconds so the JSON output stays
+/// exact and easy to compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl Stats {
+    /// `timings` must not be empty.
+    pub fn new(timings: &[Duration]) -> Self {
+        let mut nanos: Vec<u64> = timings
+            .iter()
+            .map(|timing| u64::try_from(timing.as_nanos()).unwrap_or(u64::MAX))
+            .collect();
+        nanos.sort_unstable();
+
+        let count = nanos.len();
+        let middle = count / 2;
+        let median = if count.is_multiple_of(2) {
+            (nanos[middle - 1] + nanos[middle]) / 2
+        } else {
+            nanos[middle]
+        };
+        // Nearest-rank percentile: the smallest timing that at least 95% of
+        // the runs are no slower than.
+        let p95 = nanos[(count * 95).div_ceil(100) - 1];
+
+        let mean = nanos.iter().map(|nanos| *nanos as f64).sum::<f64>() / count as f64;
+        let variance = nanos
+            .iter()
+            .map(|nanos| (*nanos as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Stats {
+            min_ns: nanos[0],
+            median_ns: median,
+            mean_ns: mean.round() as u64,
+            p95_ns: p95,
+            stddev_ns: variance.sqrt().round() as u64,
+        }
+    }
+
+    fn row(&self, day: u8, phase: String) -> Vec<String> {
+        let format = |nanos: u64| format!("{:.2?}", Duration::from_nanos(nanos));
+        vec![
+            day.to_string(),
+            phase,
+            format(self.min_ns),
+            format(self.median_ns),
+            format(self.mean_ns),
+            format(self.p95_ns),
+            format(self.stddev_ns),
+        ]
+    }
+}
+
+/// Benchmarks `part` of day `problem`, or of every day, and prints the
+/// timings, comparing them with and saving them as `baselines` asks. Fails if
+/// any median got slower than the baseline's by more than the threshold.
+pub fn run(
+    inputs: &Inputs,
+    problem: Option<u8>,
+    part: PartSelection,
+    options: BenchOptions,
+    json: bool,
+    baselines: &Baselines,
+) -> Result<ExitCode, SolutionError> {
+    // Read the baseline first so a bad path fails before the slow part.
+    let baseline = match &baselines.compare_with {
+        Some(path) => Some(BenchReport::load(path)?),
+        None => None,
+    };
+    let report = bench(inputs, problem, part, options)?;
+
+    let comparison = baseline.map(|baseline| report.compare(&baseline, baselines.threshold));
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("report serializes to JSON")
+        );
+        // Keep stdout valid JSON.
+        if let Some(comparison) = &comparison {
+            eprintln!("{}", comparison.render());
+        }
+    } else {
+        match &comparison {
+            Some(comparison) => println!("{}", comparison.render()),
+            None => println!("{}", report.render()),
+        }
+    }
+
+    if let Some(path) = &baselines.save_to {
+        report.save(path)?;
+    }
+
+    match comparison {
+        Some(comparison) if comparison.regressed() => Ok(ExitCode::FAILURE),
+        _ => Ok(ExitCode::SUCCESS),
+    }
+}
+
+/// Times `parts` of day `problem`, or of every day, against the puzzle input
+/// in `inputs`. Stops at the first day that fails to parse or solve, since its
+/// timings would mean nothing.
+pub fn bench(
+    inputs: &Inputs,
+    problem: Option<u8>,
+    part: PartSelection,
+    options: BenchOptions,
+) -> Result<BenchReport, SolutionError> {
+    let solvers: Vec<&dyn Solver> = match problem {
+        Some(problem) => {
+            vec![solutions::find(problem).ok_or(SolutionError::UnknownProblem(problem))?]
+        }
+        None => SOLUTIONS.to_vec(),
+    };
+
+    let days = solvers
+        .into_iter()
+        .map(|solver| bench_day(solver, inputs, part, options))
+        .collect::<Result<_, _>>()?;
+    Ok(BenchReport { options, days })
+}
+
+fn bench_day(
+    solver: &dyn Solver,
+    inputs: &Inputs,
+    part: PartSelection,
+    options: BenchOptions,
+) -> Result<DayBench, SolutionError> {
+    let problem_data = inputs.load(solver.day(), InputFile::Puzzle)?;
+    let parts = part.parts();
+
+    let mut parse_times = Vec::with_capacity(options.runs);
+    let mut solve_times = vec![Vec::with_capacity(options.runs); parts.len()];
+    for run in 0..options.warmup + options.runs {
+        let solved = solver.solve_parts(&problem_data, parts)?;
+        if run < options.warmup {
+            continue;
+        }
+        parse_times.push(solved.parse_time);
+        for (times, part_solved) in solve_times.iter_mut().zip(solved.parts) {
+            part_solved.answer?;
+            times.push(part_solved.solve_time);
+        }
+    }
+
+    Ok(DayBench {
+        day: solver.day(),
+        parse: Stats::new(&parse_times),
+        parts: parts
+            .iter()
+            .zip(solve_times)
+            .map(|(part, times)| PartBench {
+                part: part.number(),
+                solve: Stats::new(&times),
+            })
+            .collect(),
+    })
+}
+
+impl BenchReport {
+    pub fn load(path: &Path) -> Result<Self, SolutionError> {
+        let report = fs::read_to_string(path).map_err(|source| SolutionError::FileLoadError {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_str(&report).map_err(|err| SolutionError::InvalidFile {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SolutionError> {
+        let mut report = serde_json::to_string_pretty(self).expect("report serializes to JSON");
+        report.push('\n');
+        fs::write(path, report).map_err(|source| SolutionError::FileSaveError {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Every timed phase as (day, phase name, timings).
+    fn phases(&self) -> Vec<(u8, String, Stats)> {
+        let mut phases = Vec::new();
+        for day in &self.days {
+            phases.push((day.day, String::from("parse"), day.parse));
+            for part in &day.parts {
+                phases.push((day.day, format!("part {}", part.part), part.solve));
+            }
+        }
+        phases
+    }
+
+    pub fn render(&self) -> String {
+        let mut table = Table::new(&["Day", "Phase", "Min", "Median", "Mean", "P95", "Std dev"]);
+        for (day, phase, stats) in self.phases() {
+            table.push(stats.row(day, phase));
+        }
+        table.render()
+    }
+
+    /// Compares the median of every phase with the same phase in `baseline`.
+    pub fn compare(&self, baseline: &BenchReport, threshold: f64) -> Comparison {
+        let baseline: HashMap<(u8, String), Stats> = baseline
+            .phases()
+            .into_iter()
+            .map(|(day, phase, stats)| ((day, phase), stats))
+            .collect();
+
+        let phases = self
+            .phases()
+            .into_iter()
+            .map(|(day, phase, stats)| {
+                let baseline_ns = baseline
+                    .get(&(day, phase.clone()))
+                    .map(|stats| stats.median_ns);
+                let change = baseline_ns.map(|baseline_ns| {
+                    (stats.median_ns as f64 - baseline_ns as f64) / baseline_ns.max(1) as f64
+                        * 100.0
+                });
+                let status = match change {
+                    None => PhaseStatus::New,
+                    Some(change) if change > threshold => PhaseStatus::Slower,
+                    Some(change) if change < -threshold => PhaseStatus::Faster,
+                    Some(_) => PhaseStatus::Unchanged,
+                };
+                PhaseComparison {
+                    day,
+                    phase,
+                    baseline_ns,
+                    median_ns: stats.median_ns,
+                    change,
+                    status,
+                }
+            })
+            .collect();
+        Comparison { phases }
+    }
+}
+
+/// The medians of a run next to the ones in a baseline.
+pub struct Comparison {
+    phases: Vec<PhaseComparison>,
+}
+
+struct PhaseComparison {
+    day: u8,
+    phase: String,
+    baseline_ns: Option<u64>,
+    median_ns: u64,
+    /// Change from the baseline's median, in percent.
+    change: Option<f64>,
+    status: PhaseStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PhaseStatus {
+    Unchanged,
+    Faster,
+    /// Slower by more than the threshold.
+    Slower,
+    /// Not in the baseline.
+    New,
+}
+
+impl Comparison {
+    pub fn regressed(&self) -> bool {
+        self.phases
+            .iter()
+            .any(|phase| phase.status == PhaseStatus::Slower)
+    }
+
+    pub fn render(&self) -> String {
+        let format = |nanos: u64| format!("{:.2?}", Duration::from_nanos(nanos));
+        let mut table = Table::new(&["Day", "Phase", "Baseline", "Median", "Change", "Status"]);
+        for phase in &self.phases {
+            table.push(vec![
+                phase.day.to_string(),
+                phase.phase.clone(),
+                phase.baseline_ns.map_or(String::from("-"), format),
+                format(phase.median_ns),
+                phase
+                    .change
+                    .map_or(String::from("-"), |change| format!("{:+.1}%", change)),
+                String::from(match phase.status {
+                    PhaseStatus::Unchanged => "ok",
+                    PhaseStatus::Faster => "faster",
+                    PhaseStatus::Slower => "slower",
+                    PhaseStatus::New => "new",
+                }),
+            ]);
+        }
+        table.render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_of_timings() {
+        let timings: Vec<Duration> = [5, 1, 4, 2, 3, 7, 6, 8, 9, 10]
+            .into_iter()
+            .map(Duration::from_nanos)
+            .collect();
+
+        assert_eq!(
+            Stats::new(&timings),
+            Stats {
+                min_ns: 1,
+                median_ns: 5,
+                mean_ns: 6,
+                p95_ns: 10,
+                stddev_ns: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_compare_flags_slower_phases() {
+        let report = |parse_ns, part_one_ns| {
+            let stats = |median_ns| Stats {
+                min_ns: median_ns,
+                median_ns,
+                mean_ns: median_ns,
+                p95_ns: median_ns,
+                stddev_ns: 0,
+            };
+            BenchReport {
+                options: BenchOptions { warmup: 0, runs: 1 },
+                days: vec![DayBench {
+                    day: 3,
+                    parse: stats(parse_ns),
+                    parts: vec![PartBench {
+                        part: 1,
+                        solve: stats(part_one_ns),
+                    }],
+                }],
+            }
+        };
+        let baseline = report(100, 100);
+
+        let unchanged = report(105, 80).compare(&baseline, 10.0);
+        assert!(!unchanged.regressed());
+        assert_eq!(unchanged.phases[1].status, PhaseStatus::Faster);
+
+        let doubled = report(100, 200).compare(&baseline, 10.0);
+        assert!(doubled.regressed());
+        assert_eq!(doubled.phases[1].status, PhaseStatus::Slower);
+    }
+}
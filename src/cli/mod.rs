@@ -0,0 +1,289 @@
+pub mod answers;
+pub mod bench;
+pub mod run;
+pub mod run_all;
+mod table;
+
+use std::{
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use clap::{builder::RangedU64ValueParser, error::ErrorKind, CommandFactory, Parser, Subcommand};
+
+use crate::{
+    error::{diagnostic::Diagnostic, SolutionError},
+    server::ServerOptions,
+    solutions,
+    util::{
+        inputs::{Inputs, INPUTS_ENV_VAR},
+        PartSelection,
+    },
+};
+
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Directory holding the inputs, laid out as `<inputs>/2023/day05.txt`
+    /// [default: `inputs` from aoc.json, or ./inputs]
+    #[arg(long, global = true, env = INPUTS_ENV_VAR)]
+    pub inputs: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Solve one part of a day's puzzle and print the answer
+    Run {
+        /// Day of the puzzle to solve
+        #[arg(value_parser = parse_problem)]
+        problem: u8,
+        /// Part of the puzzle to solve, or both from a single parse
+        part: PartSelection,
+        /// Path to the puzzle input, or `-` to read it from stdin [default: the
+        /// day's file in the inputs directory]
+        data_path: Option<PathBuf>,
+        /// Solve this text instead of reading an input
+        #[arg(long, value_name = "TEXT", conflicts_with = "data_path")]
+        input_text: Option<String>,
+        /// Solve the day's numbered example from the puzzle text instead of its
+        /// input, and check the answer when it is known [default N: 1]
+        #[arg(
+            long,
+            value_name = "N",
+            num_args = 0..=1,
+            default_missing_value = "1",
+            conflicts_with_all = ["data_path", "input_text"]
+        )]
+        example: Option<u8>,
+    },
+    /// Solve both parts of every day and print a table of the results
+    RunAll {
+        /// Number of days to solve at the same time
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+    },
+    /// Record the accepted answer for a day, for `verify` to check against
+    Record {
+        /// Day the answer is for
+        #[arg(value_parser = parse_problem)]
+        problem: u8,
+        /// Part the answer is for
+        part: PartSelection,
+        /// The accepted answer [default: solve the day's input and record its answer]
+        answer: Option<String>,
+    },
+    /// Solve every day and check the answers against the recorded ones
+    Verify {
+        /// Number of days to solve at the same time
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+    },
+    /// Time the parse and solve steps of a day, or of every day, over repeated runs
+    Bench {
+        /// Day to benchmark [default: every day]
+        #[arg(value_parser = parse_problem)]
+        problem: Option<u8>,
+        /// Part of the puzzle to benchmark
+        #[arg(default_value = "both")]
+        part: PartSelection,
+        /// Number of timed runs
+        #[arg(short, long, default_value_t = 10, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        runs: usize,
+        /// Number of untimed runs before the timed ones
+        #[arg(short, long, default_value_t = 1)]
+        warmup: usize,
+        /// Print the statistics as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+        /// Compare the medians with a baseline saved by `--save-baseline`
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+        /// Save the statistics as a baseline for later runs to compare with
+        #[arg(long, value_name = "FILE")]
+        save_baseline: Option<PathBuf>,
+        /// How much slower than the baseline, in percent, a median may get
+        /// before the run fails
+        #[arg(
+            long,
+            value_name = "PERCENT",
+            default_value_t = 10.0,
+            requires = "baseline"
+        )]
+        threshold: f64,
+    },
+    /// Serve the solutions over HTTP
+    Serve {
+        /// Address to listen on [default: 0.0.0.0]
+        #[arg(long)]
+        addr: Option<Ipv4Addr>,
+        /// Port to listen on [default: 3000]
+        #[arg(long)]
+        port: Option<u16>,
+        /// Seconds a request may spend solving before it fails
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+        /// How many answers to cache, or 0 to cache none
+        #[arg(long, default_value_t = 256)]
+        cache_size: usize,
+        /// Seconds a cached answer is served for
+        #[arg(long, default_value_t = 3600)]
+        cache_ttl: u64,
+        /// How many jobs run at once [default: the number of CPUs]
+        #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        job_workers: Option<usize>,
+        /// How many jobs may wait for a worker before new ones are turned away
+        #[arg(long, default_value_t = 64)]
+        max_queued_jobs: usize,
+        /// How many items of a batch are solved at once [default: the number
+        /// of CPUs]
+        #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        batch_concurrency: Option<usize>,
+        /// Serve the unauthenticated `/admin` routes, such as
+        /// `DELETE /admin/cache`
+        #[arg(long)]
+        admin: bool,
+    },
+}
+
+/// Runs the command `args` asks for and returns the process exit code.
+pub async fn run(args: Cli) -> ExitCode {
+    if let Err(err) = check_args(&args) {
+        let _ = err.print();
+        return ExitCode::from(err.exit_code() as u8);
+    }
+
+    let inputs = match Inputs::resolve(args.inputs) {
+        Ok(inputs) => inputs,
+        Err(err) => {
+            print_error_message(&err, None);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match args.command {
+        Command::Run {
+            problem,
+            part,
+            data_path,
+            input_text,
+            example,
+        } => return run::run_and_print(&inputs, problem, part, data_path, input_text, example),
+        Command::RunAll { jobs } => return run_all::run_all(&inputs, jobs),
+        Command::Record {
+            problem,
+            part,
+            answer,
+        } => {
+            return answers::record(&inputs, problem, part, answer).unwrap_or_else(|err| {
+                print_error_message(&err, None);
+                ExitCode::FAILURE
+            })
+        }
+        Command::Verify { jobs } => {
+            return answers::verify(&inputs, jobs).unwrap_or_else(|err| {
+                print_error_message(&err, None);
+                ExitCode::FAILURE
+            })
+        }
+        Command::Bench {
+            problem,
+            part,
+            runs,
+            warmup,
+            json,
+            baseline,
+            save_baseline,
+            threshold,
+        } => {
+            let options = bench::BenchOptions { warmup, runs };
+            let baselines = bench::Baselines {
+                compare_with: baseline,
+                save_to: save_baseline,
+                threshold,
+            };
+            return bench::run(&inputs, problem, part, options, json, &baselines).unwrap_or_else(
+                |err| {
+                    print_error_message(&err, None);
+                    ExitCode::FAILURE
+                },
+            );
+        }
+        Command::Serve {
+            addr,
+            port,
+            timeout,
+            cache_size,
+            cache_ttl,
+            job_workers,
+            max_queued_jobs,
+            batch_concurrency,
+            admin,
+        } => {
+            let defaults = ServerOptions::default();
+            let options = ServerOptions {
+                timeout: Duration::from_secs(timeout),
+                cache_size,
+                cache_ttl: Duration::from_secs(cache_ttl),
+                job_workers: job_workers.unwrap_or(defaults.job_workers),
+                max_queued_jobs,
+                batch_concurrency: batch_concurrency.unwrap_or(defaults.batch_concurrency),
+                admin,
+            };
+            crate::server::serve(addr, port, options).await
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Rejects the combinations of arguments clap can't express on its own, the
+/// same way clap rejects any other bad arguments.
+fn check_args(args: &Cli) -> Result<(), clap::Error> {
+    if let Command::Record {
+        part: PartSelection::Both,
+        answer: Some(_),
+        ..
+    } = args.command
+    {
+        return Err(Cli::command().error(
+            ErrorKind::ArgumentConflict,
+            "an answer can only be recorded for one part at a time; give part 1 or 2",
+        ));
+    }
+    Ok(())
+}
+
+/// Prints `err_type`. Parse errors are rendered against the input they came
+/// from when `input` (the input's path and contents) is given.
+fn print_error_message(err_type: &SolutionError, input: Option<(&Path, &str)>) {
+    if let (SolutionError::DataParsingError(err), Some((path, problem_data))) = (err_type, input) {
+        let diagnostic = Diagnostic::new(err, problem_data);
+        eprintln!("{}", diagnostic.render(&path.display().to_string()));
+        return;
+    }
+    eprintln!("Failed to compute solution with error: {}", err_type);
+}
+
+/// Accepts only days that have a registered solution.
+fn parse_problem(value: &str) -> Result<u8, String> {
+    let day = value
+        .parse::<u8>()
+        .map_err(|_| format!("`{}` is not a day number", value))?;
+    match solutions::find(day) {
+        Some(_) => Ok(day),
+        None => {
+            let days: Vec<String> = solutions::SOLUTIONS
+                .iter()
+                .map(|solver| solver.day().to_string())
+                .collect();
+            Err(format!(
+                "day {} has no solution (available: {})",
+                day,
+                days.join(", ")
+            ))
+        }
+    }
+}
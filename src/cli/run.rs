@@ -0,0 +1,222 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, StdinLock},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+};
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+use crate::{
+    error::{diagnostic::Diagnostic, SolutionError},
+    solutions::{self, Example, Solved, Solver},
+    util::{
+        inputs::{InputFile, Inputs},
+        load_file, open_file,
+        progress::{self, Progress, ProgressSink, Step},
+        text::LastLine,
+        PartSelection, SolutionPart,
+    },
+};
+
+use super::print_error_message;
+
+/// The `data_path` that reads the input from stdin.
+const STDIN_PATH: &str = "-";
+
+fn print_solved_message(answer: String) {
+    println!("Got an answer! Answer is: {}", answer);
+}
+
+/// Where `run` reads a day's input from.
+enum ProblemData {
+    /// A file, which is parsed as it is read and only read whole again to
+    /// show where a parse error is.
+    File {
+        path: PathBuf,
+        reader: BufReader<File>,
+    },
+    /// Input that is already in memory, with a name for it in errors.
+    Text { origin: PathBuf, data: String },
+    /// Stdin, which is parsed as it is read. Only its last line is kept, to
+    /// show a parse error in the line a streaming day stopped at.
+    Stdin(LastLine<StdinLock<'static>>),
+}
+
+impl ProblemData {
+    /// Picks the input from, in order, `data_path` (stdin when it is `-`),
+    /// `input_text`, `example` (from the day's embedded examples, falling back
+    /// to the inputs directory) or the day's puzzle input.
+    fn open(
+        inputs: &Inputs,
+        solver: &dyn Solver,
+        data_path: Option<PathBuf>,
+        input_text: Option<String>,
+        example: Option<u8>,
+    ) -> Result<Self, SolutionError> {
+        if let Some(path) = data_path {
+            if path == Path::new(STDIN_PATH) {
+                return Ok(ProblemData::Stdin(LastLine::new(io::stdin().lock())));
+            }
+            let reader = open_file(path.clone())?;
+            return Ok(ProblemData::File { path, reader });
+        }
+        if let Some(data) = input_text {
+            return Ok(ProblemData::Text {
+                origin: PathBuf::from("<input text>"),
+                data,
+            });
+        }
+        if let Some(embedded) = example.and_then(|example| embedded_example(solver, example)) {
+            let origin = format!("<day {} example {}>", solver.day(), example.unwrap());
+            return Ok(ProblemData::Text {
+                origin: PathBuf::from(origin),
+                data: embedded.input.to_string(),
+            });
+        }
+        let file = example.map_or(InputFile::Puzzle, InputFile::Example);
+        Ok(ProblemData::File {
+            path: inputs.path(solver.day(), file),
+            reader: inputs.open(solver.day(), file)?,
+        })
+    }
+
+    fn solve(
+        &mut self,
+        solver: &dyn Solver,
+        parts: &[SolutionPart],
+    ) -> Result<Solved, SolutionError> {
+        match self {
+            ProblemData::File { reader, .. } => solver.solve_parts_from_reader(reader, parts),
+            ProblemData::Text { data, .. } => solver.solve_parts(data, parts),
+            ProblemData::Stdin(reader) => solver.solve_parts_from_reader(reader, parts),
+        }
+    }
+
+    /// Prints `err`, pointing a parse error at the input it came from.
+    fn print_error(&self, err: &SolutionError) {
+        match self {
+            ProblemData::File { path, .. } => {
+                let data = match err {
+                    SolutionError::DataParsingError(_) => load_file(path.clone()).ok(),
+                    _ => None,
+                };
+                print_error_message(err, data.as_deref().map(|data| (path.as_path(), data)));
+            }
+            ProblemData::Text { origin, data } => print_error_message(err, Some((origin, data))),
+            ProblemData::Stdin(reader) => match (err, reader.get()) {
+                (SolutionError::DataParsingError(parse_err), Some((line_number, line)))
+                    if parse_err.line == line_number =>
+                {
+                    let diagnostic = Diagnostic::for_line(parse_err, &line);
+                    eprintln!("{}", diagnostic.render("<stdin>"));
+                }
+                _ => print_error_message(err, None),
+            },
+        }
+    }
+}
+
+/// A progress bar on stderr for the solver's progress reports. It stays
+/// hidden until the solver reports how much work it has, so days that never
+/// report don't flash an empty bar, and indicatif keeps it hidden when stderr
+/// isn't a terminal.
+fn progress_bar() -> (ProgressBar, ProgressSink) {
+    let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::hidden()).with_style(
+        ProgressStyle::with_template("{msg:>7} [{bar:40}] {human_pos}/{human_len} ({eta})")
+            .expect("progress template is valid")
+            .progress_chars("=> "),
+    );
+    let sink_bar = bar.clone();
+    let sink: ProgressSink = Arc::new(move |progress: &Progress| {
+        let step = match progress.step {
+            Step::Parse => String::from("parse"),
+            Step::Part { part } => format!("part {}", part),
+        };
+        sink_bar.set_message(step);
+        match progress.total {
+            Some(total) => {
+                if sink_bar.is_hidden() {
+                    sink_bar.set_draw_target(ProgressDrawTarget::stderr());
+                }
+                sink_bar.set_length(total);
+                sink_bar.set_position(progress.done);
+            }
+            None => sink_bar.reset(),
+        }
+    });
+    (bar, sink)
+}
+
+fn embedded_example(solver: &dyn Solver, example: u8) -> Option<&'static Example> {
+    solver.examples().get(usize::from(example).checked_sub(1)?)
+}
+
+/// Solves `part` of day `problem` and prints the answers, checking them when
+/// solving one of the day's embedded examples. Fails when the input can't be
+/// opened or solved, a part errors or an example's answer doesn't match.
+pub fn run_and_print(
+    inputs: &Inputs,
+    problem: u8,
+    part: PartSelection,
+    data_path: Option<PathBuf>,
+    input_text: Option<String>,
+    example: Option<u8>,
+) -> ExitCode {
+    let Some(solver) = solutions::find(problem) else {
+        print_error_message(&SolutionError::UnknownProblem(problem), None);
+        return ExitCode::FAILURE;
+    };
+
+    // Only an embedded example knows what its answers should be.
+    let expected_answers = match (&data_path, &input_text, example) {
+        (None, None, Some(example)) => embedded_example(solver, example),
+        _ => None,
+    };
+    let mut problem_data = match ProblemData::open(inputs, solver, data_path, input_text, example) {
+        Ok(problem_data) => problem_data,
+        Err(err) => {
+            print_error_message(&err, None);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("Day {}: {}", solver.day(), solver.title());
+    let (bar, sink) = progress_bar();
+    let solved = progress::scope(sink, || problem_data.solve(solver, part.parts()));
+    bar.finish_and_clear();
+    let solved = match solved {
+        Ok(solved) => solved,
+        Err(err) => {
+            problem_data.print_error(&err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut exit_code = ExitCode::SUCCESS;
+    for part_solved in solved.parts {
+        if part == PartSelection::Both {
+            print!("Part {}: ", part_solved.part.number());
+        }
+        let expected = expected_answers.and_then(|example| example.expected(part_solved.part));
+        match part_solved.answer {
+            Ok(answer) => {
+                let matches_expected = expected.map(|expected| expected == answer);
+                print_solved_message(answer);
+                match (matches_expected, expected) {
+                    (Some(true), _) => println!("Matches the example's expected answer."),
+                    (Some(false), Some(expected)) => {
+                        println!("The example's expected answer is {}!", expected);
+                        exit_code = ExitCode::FAILURE;
+                    }
+                    _ => {}
+                }
+            }
+            Err(err) => {
+                problem_data.print_error(&err);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+    exit_code
+}
@@ -0,0 +1,124 @@
+use std::{
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+use crate::{
+    error::SolutionError,
+    solutions::{Solved, Solver, SOLUTIONS},
+    util::{
+        inputs::{InputFile, Inputs},
+        PartSelection,
+    },
+};
+
+use super::table::Table;
+
+/// Solves both parts of every registered day against its input in
+/// `inputs`, using up to `jobs` threads, and prints a table of the results.
+/// Fails if any part errored or panicked.
+pub fn run_all(inputs: &Inputs, jobs: usize) -> ExitCode {
+    let results = run_days(SOLUTIONS, inputs, jobs);
+
+    let mut table = Table::new(&["Day", "Part", "Answer", "Parse", "Solve", "Status"]);
+    let mut failed = false;
+    for (solver, result) in SOLUTIONS.iter().zip(results) {
+        match result {
+            Ok(solved) => {
+                for part_solved in solved.parts {
+                    let (answer, status) = match part_solved.answer {
+                        Ok(answer) => (answer, String::from("ok")),
+                        Err(err) => {
+                            failed = true;
+                            (String::from("-"), status_of(&err))
+                        }
+                    };
+                    table.push(vec![
+                        solver.day().to_string(),
+                        part_solved.part.number().to_string(),
+                        answer,
+                        format!("{:.2?}", solved.parse_time),
+                        format!("{:.2?}", part_solved.solve_time),
+                        status,
+                    ]);
+                }
+            }
+            Err(err) => {
+                failed = true;
+                for part in PartSelection::Both.parts() {
+                    table.push(vec![
+                        solver.day().to_string(),
+                        part.number().to_string(),
+                        String::from("-"),
+                        String::from("-"),
+                        String::from("-"),
+                        status_of(&err),
+                    ]);
+                }
+            }
+        }
+    }
+    println!("{}", table.render());
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+pub(super) fn status_of(err: &SolutionError) -> String {
+    match err {
+        SolutionError::Panicked(message) => format!("panicked: {}", message),
+        _ => format!("error: {}", err),
+    }
+}
+
+fn run_day(solver: &dyn Solver, inputs: &Inputs) -> Result<Solved, SolutionError> {
+    let problem_data = inputs.load(solver.day(), InputFile::Puzzle)?;
+    solver.solve_parts(&problem_data, PartSelection::Both.parts())
+}
+
+/// Runs every solver, returning the results in the same order as `solvers`.
+/// With more than one job the days are handed out to a pool of worker
+/// threads as they free up.
+pub(super) fn run_days(
+    solvers: &[&dyn Solver],
+    inputs: &Inputs,
+    jobs: usize,
+) -> Vec<Result<Solved, SolutionError>> {
+    if jobs <= 1 {
+        return solvers
+            .iter()
+            .map(|solver| run_day(*solver, inputs))
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let slots: Mutex<Vec<Option<Result<Solved, SolutionError>>>> =
+        Mutex::new(solvers.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(solvers.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(solver) = solvers.get(index) else {
+                    break;
+                };
+                let result = run_day(*solver, inputs);
+                slots.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    slots
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|slot| slot.expect("every day is run by a worker"))
+        .collect()
+}
@@ -0,0 +1,69 @@
+/// A plain text table whose columns are as wide as their widest cell.
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn render(&self) -> String {
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|column| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(column))
+                    .chain([&self.headers[column]])
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let render_row = |row: &[String]| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            cells.join("  ").trim_end().to_string()
+        };
+
+        let mut lines = vec![render_row(&self.headers)];
+        lines.push(
+            widths
+                .iter()
+                .map(|width| "-".repeat(*width))
+                .collect::<Vec<String>>()
+                .join("  "),
+        );
+        lines.extend(self.rows.iter().map(|row| render_row(row)));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_fit_widest_cell() {
+        let mut table = Table::new(&["Day", "Answer"]);
+        table.push(vec![String::from("1"), String::from("57346")]);
+        table.push(vec![String::from("12"), String::from("-")]);
+
+        assert_eq!(
+            table.render(),
+            "Day  Answer\n---  ------\n1    57346\n12   -"
+        );
+    }
+}
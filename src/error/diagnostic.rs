@@ -0,0 +1,116 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use super::ParseError;
+use crate::util::text::lines;
+
+/// A `ParseError` together with the input line it points into, ready to be
+/// shown to a person (`render`) or sent back to a client as JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    /// Number of characters from `column` that are underlined.
+    pub length: usize,
+    pub source_line: String,
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl Diagnostic {
+    pub fn new(err: &ParseError, source: &str) -> Self {
+        let source_line = lines(source)
+            .nth(err.line.saturating_sub(1))
+            .map(|(_, line)| line)
+            .unwrap_or_default();
+        Diagnostic::for_line(err, source_line)
+    }
+
+    /// `new` for when only the line `err` points into is at hand, such as for
+    /// an input that was streamed.
+    pub fn for_line(err: &ParseError, source_line: &str) -> Self {
+        let source_line = source_line.to_string();
+        Diagnostic {
+            day: err.day,
+            line: err.line,
+            column: err.column,
+            length: err.snippet.chars().count().max(1),
+            source_line,
+            snippet: err.snippet.clone(),
+            expected: err.expected.clone(),
+        }
+    }
+
+    /// Renders the diagnostic the way rustc does, with `origin` (usually the
+    /// input file's path) in the location line.
+    ///
+    /// ```text
+    /// error: failed to parse input for day 2
+    ///  --> inputs/day02.txt:3:16
+    ///   |
+    /// 3 | Game 3: 1 red; 8 gren, 2 blue
+    ///   |                ^^^^^^ unexpected `8 gren`
+    ///   |
+    ///   = hint: expected `<n> red|green|blue`
+    /// ```
+    pub fn render(&self, origin: &str) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let label = if self.snippet.is_empty() {
+            String::from("unexpected end of line")
+        } else {
+            format!("unexpected `{}`", self.snippet)
+        };
+
+        let mut rendered = String::new();
+        let _ = writeln!(
+            rendered,
+            "error: failed to parse input for day {}",
+            self.day
+        );
+        let _ = writeln!(
+            rendered,
+            "{}--> {}:{}:{}",
+            gutter, origin, self.line, self.column
+        );
+        let _ = writeln!(rendered, "{} |", gutter);
+        let _ = writeln!(rendered, "{} | {}", self.line, self.source_line);
+        let _ = writeln!(
+            rendered,
+            "{} | {}{} {}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.length),
+            label
+        );
+        let _ = writeln!(rendered, "{} |", gutter);
+        let _ = write!(rendered, "{} = hint: expected {}", gutter, self.expected);
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_underlines_snippet() {
+        let source = "Game 1: 2 red\nGame 2: 1 red; 8 gren, 2 blue";
+        let line = source.split('\n').nth(1).unwrap();
+        let err = ParseError::new(2, 2, line, &line[15..21], "`<n> red|green|blue`");
+
+        let rendered = Diagnostic::new(&err, source).render("day02.txt");
+
+        assert_eq!(
+            rendered,
+            "error: failed to parse input for day 2\n \
+             --> day02.txt:2:16\n  \
+             |\n\
+             2 | Game 2: 1 red; 8 gren, 2 blue\n  \
+             |                ^^^^^^ unexpected `8 gren`\n  \
+             |\n  \
+             = hint: expected `<n> red|green|blue`"
+        );
+    }
+}
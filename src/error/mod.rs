@@ -1,7 +1,138 @@
+pub mod diagnostic;
+
+use std::{error::Error, fmt, io, path::PathBuf};
+
 #[derive(Debug)]
 pub enum SolutionError {
     NoPossibleSolution,
-    FileLoadError,
-    DataParsingError,
-    UnknownProblem,
+    FileLoadError {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// The inputs directory has no file where the day's input should be.
+    MissingInput {
+        day: u8,
+        path: PathBuf,
+    },
+    FileSaveError {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// Reading the input from a stream such as stdin failed.
+    InputReadError(io::Error),
+    /// A file the tool reads its own settings or records from is malformed.
+    InvalidFile {
+        path: PathBuf,
+        message: String,
+    },
+    DataParsingError(ParseError),
+    UnknownProblem(u8),
+    /// The solver panicked; holds the panic message.
+    Panicked(String),
+    /// The solver stopped at a `checkpoint` because its work was given up on.
+    Cancelled,
 }
+
+impl fmt::Display for SolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolutionError::NoPossibleSolution => {
+                write!(f, "no solution is possible given the provided data")
+            }
+            SolutionError::FileLoadError { path, source } => {
+                write!(f, "failed to load {}: {}", path.display(), source)
+            }
+            SolutionError::MissingInput { day, path } => write!(
+                f,
+                "no input for day {}: expected a file at {}",
+                day,
+                path.display()
+            ),
+            SolutionError::FileSaveError { path, source } => {
+                write!(f, "failed to save {}: {}", path.display(), source)
+            }
+            SolutionError::InputReadError(source) => write!(f, "failed to read input: {}", source),
+            SolutionError::InvalidFile { path, message } => {
+                write!(f, "invalid contents in {}: {}", path.display(), message)
+            }
+            SolutionError::DataParsingError(err) => write!(f, "{}", err),
+            SolutionError::UnknownProblem(day) => write!(f, "day {} has no solution", day),
+            SolutionError::Panicked(message) => write!(f, "solver panicked: {}", message),
+            SolutionError::Cancelled => write!(f, "solving was cancelled"),
+        }
+    }
+}
+
+impl Error for SolutionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolutionError::FileLoadError { source, .. }
+            | SolutionError::FileSaveError { source, .. }
+            | SolutionError::InputReadError(source) => Some(source),
+            SolutionError::DataParsingError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for SolutionError {
+    fn from(err: ParseError) -> Self {
+        SolutionError::DataParsingError(err)
+    }
+}
+
+/// A piece of puzzle input that a day's parser could not make sense of.
+///
+/// `line` and `column` are 1-based and point at the start of `snippet`, the
+/// part of the line that was rejected. `expected` describes what the parser
+/// wanted to find there instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// `snippet` has to be a slice of `line` so that its column can be worked
+    /// out from where it sits in the line.
+    pub fn new(
+        day: u8,
+        line_number: usize,
+        line: &str,
+        snippet: &str,
+        expected: impl Into<String>,
+    ) -> Self {
+        let offset = (snippet.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| *offset <= line.len())
+            .unwrap_or(0);
+
+        ParseError {
+            day,
+            line: line_number,
+            column: line[..offset].chars().count() + 1,
+            snippet: snippet.to_string(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        if self.snippet.is_empty() {
+            write!(f, ", found end of line")
+        } else {
+            write!(f, ", found `{}`", self.snippet)
+        }
+    }
+}
+
+impl Error for ParseError {}
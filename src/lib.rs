@@ -0,0 +1,29 @@
+//! Solutions to the Advent of Code 2023 puzzles.
+//!
+//! Every day implements [`Solution`] and is registered in [`SOLUTIONS`], where
+//! it can be looked up by day with [`find`]:
+//!
+//! ```
+//! use advent_of_code::{find, SolutionPart};
+//!
+//! let solver = find(6).unwrap();
+//! let solved = solver
+//!     .solve_parts("Time: 7 15 30\nDistance: 9 40 200", &[SolutionPart::PartOne])
+//!     .unwrap();
+//! assert_eq!(solved.parts[0].answer.as_deref().unwrap(), "288");
+//! ```
+//!
+//! A day's parsed model is available from its [`Solution::parse`], for
+//! example [`solutions::two::DayTwo`] parses to [`solutions::two::Game`]s.
+//! [`serve`] runs the HTTP API, and [`cli`] is the `advent-of-code` binary.
+
+pub mod cli;
+pub mod error;
+pub mod server;
+pub mod solutions;
+pub mod util;
+
+pub use error::{ParseError, SolutionError};
+pub use server::serve;
+pub use solutions::{find, Solution, Solved, Solver, SOLUTIONS};
+pub use util::{PartSelection, SolutionPart};
@@ -0,0 +1,66 @@
+use axum::{extract::State, Json};
+use futures_util::{stream, StreamExt};
+use serde::Serialize;
+
+use crate::util::cancel::CancelToken;
+
+use super::{
+    answer,
+    error::ApiError,
+    request::{BatchRequest, SolveRequest},
+    timed_out, AppState, Problem, ProblemOutput,
+};
+
+/// One item of a `/solve/batch` response: the status `/solve` would have
+/// answered the item with, and its answers or error.
+#[derive(Serialize)]
+pub(super) struct BatchItemOutput {
+    status: u16,
+    #[serde(flatten)]
+    output: Option<ProblemOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ApiError>,
+}
+
+/// `POST /solve/batch`: solves every item of a JSON array of `/solve` bodies,
+/// up to `batch_concurrency` at a time, and answers with their results in the
+/// same order. Items fail on their own, so the batch itself always succeeds
+/// once it is read.
+pub(super) async fn solve_batch(
+    State(state): State<AppState>,
+    BatchRequest(items): BatchRequest,
+) -> Json<Vec<BatchItemOutput>> {
+    let outputs = stream::iter(items)
+        .map(|item| solve_item(&state, item))
+        .buffered(state.options.batch_concurrency.max(1))
+        .collect()
+        .await;
+    Json(outputs)
+}
+
+async fn solve_item(state: &AppState, item: Result<SolveRequest, ApiError>) -> BatchItemOutput {
+    // Like `/solve`, an item's solver is cancelled when it times out or the
+    // client goes away.
+    let token = CancelToken::default();
+    let _cancel = token.cancel_on_drop();
+
+    let answered = async {
+        let problem = Problem::new(state, item?)?;
+        let day = problem.day;
+        tokio::time::timeout(state.options.timeout, answer(state, problem, token, None))
+            .await
+            .map_err(|_| timed_out(day, state.options.timeout))?
+    };
+    match answered.await {
+        Ok(answered) => BatchItemOutput {
+            status: 200,
+            output: Some(answered.output),
+            error: None,
+        },
+        Err(err) => BatchItemOutput {
+            status: err.code.status().as_u16(),
+            output: None,
+            error: Some(err),
+        },
+    }
+}
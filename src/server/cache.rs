@@ -0,0 +1,167 @@
+use std::{
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::State,
+    http::{HeaderName, HeaderValue},
+    response::{IntoResponseParts, ResponseParts},
+    Json,
+};
+use lru::LruCache;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::util::{text, SolutionPart};
+
+use super::AppState;
+
+/// The header that says whether a `/solve` answer came from the cache.
+pub const CACHE_HEADER: HeaderName = HeaderName::from_static("x-cache");
+
+/// SHA-256 of an input's lines, so that inputs that only differ in a byte
+/// order mark, line endings or trailing blank lines share cached answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct InputHash([u8; 32]);
+
+impl InputHash {
+    pub(super) fn new(input: &str) -> Self {
+        let mut hasher = Sha256::new();
+        for (_, line) in text::lines(input) {
+            hasher.update(line);
+            hasher.update(b"\n");
+        }
+        InputHash(hasher.finalize().into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CacheKey {
+    day: u8,
+    part: SolutionPart,
+    input: InputHash,
+}
+
+struct CachedAnswer {
+    answer: String,
+    stored_at: Instant,
+}
+
+/// The answers `/solve` has found, with the least recently used dropped once
+/// there are more than the cache holds and any dropped once older than its
+/// time to live. Only answers are kept: errors are cheap to find again.
+pub(super) struct AnswerCache {
+    /// `None` when caching is turned off.
+    answers: Option<Mutex<LruCache<CacheKey, CachedAnswer>>>,
+    ttl: Duration,
+}
+
+impl AnswerCache {
+    /// A cache of up to `capacity` answers, none of them kept for longer than
+    /// `ttl`. A capacity of 0 caches nothing.
+    pub(super) fn new(capacity: usize, ttl: Duration) -> Self {
+        AnswerCache {
+            answers: NonZeroUsize::new(capacity)
+                .map(|capacity| Mutex::new(LruCache::new(capacity))),
+            ttl,
+        }
+    }
+
+    pub(super) fn get(&self, day: u8, part: SolutionPart, input: InputHash) -> Option<String> {
+        let mut answers = self.answers.as_ref()?.lock().unwrap();
+        let key = CacheKey { day, part, input };
+        let cached = answers.get(&key)?;
+        if cached.stored_at.elapsed() > self.ttl {
+            answers.pop(&key);
+            return None;
+        }
+        Some(cached.answer.clone())
+    }
+
+    pub(super) fn insert(&self, day: u8, part: SolutionPart, input: InputHash, answer: String) {
+        let Some(answers) = &self.answers else {
+            return;
+        };
+        let cached = CachedAnswer {
+            answer,
+            stored_at: Instant::now(),
+        };
+        answers
+            .lock()
+            .unwrap()
+            .put(CacheKey { day, part, input }, cached);
+    }
+
+    /// Drops every answer and returns how many there were.
+    pub(super) fn clear(&self) -> usize {
+        let Some(answers) = &self.answers else {
+            return 0;
+        };
+        let mut answers = answers.lock().unwrap();
+        let cleared = answers.len();
+        answers.clear();
+        cleared
+    }
+}
+
+/// Whether a response's answers came from the cache, sent as `x-cache`.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+impl IntoResponseParts for CacheStatus {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        let value = match self {
+            CacheStatus::Hit => "HIT",
+            CacheStatus::Miss => "MISS",
+        };
+        res.headers_mut()
+            .insert(CACHE_HEADER, HeaderValue::from_static(value));
+        Ok(res)
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct CacheCleared {
+    cleared: usize,
+}
+
+/// `DELETE /admin/cache`: forgets every cached answer.
+pub(super) async fn clear_cache(State(state): State<AppState>) -> Json<CacheCleared> {
+    Json(CacheCleared {
+        cleared: state.cache.clear(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_evicts_least_recently_used_and_expired() {
+        let input = InputHash::new("1 2 3\r\n\r\n");
+        assert_eq!(input, InputHash::new("\u{feff}1 2 3\n"));
+
+        let cache = AnswerCache::new(2, Duration::from_secs(60));
+        cache.insert(1, SolutionPart::PartOne, input, String::from("one"));
+        cache.insert(2, SolutionPart::PartOne, input, String::from("two"));
+        assert!(cache.get(1, SolutionPart::PartOne, input).is_some());
+        cache.insert(3, SolutionPart::PartOne, input, String::from("three"));
+        assert_eq!(cache.get(2, SolutionPart::PartOne, input), None);
+        assert_eq!(
+            cache.get(1, SolutionPart::PartOne, input).as_deref(),
+            Some("one")
+        );
+
+        let expired = AnswerCache::new(2, Duration::ZERO);
+        expired.insert(1, SolutionPart::PartOne, input, String::from("one"));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(expired.get(1, SolutionPart::PartOne, input), None);
+    }
+}
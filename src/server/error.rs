@@ -0,0 +1,129 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    error::{diagnostic::Diagnostic, SolutionError},
+    util::SolutionPart,
+};
+
+use super::request_id;
+
+/// What went wrong, for clients to match on instead of the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The request couldn't be read, such as malformed JSON or a missing
+    /// parameter.
+    BadRequest,
+    UnsupportedMediaType,
+    /// The part is not 1, 2 or "both".
+    InvalidPart,
+    /// No day with that number has a solution.
+    UnknownProblem,
+    /// No job has that ID, or it finished too long ago to be kept.
+    UnknownJob,
+    /// The input couldn't be parsed; `location` says where.
+    InvalidInput,
+    /// The input parsed, but has no answer.
+    NoSolution,
+    /// The solver panicked.
+    Panicked,
+    /// Solving took longer than the server allows.
+    Timeout,
+    /// Too many jobs are waiting for a worker to take another.
+    QueueFull,
+    /// The solve was cancelled before it finished, such as by
+    /// `DELETE /jobs/{id}`.
+    Cancelled,
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::BadRequest | ErrorCode::InvalidPart => StatusCode::BAD_REQUEST,
+            ErrorCode::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ErrorCode::UnknownProblem | ErrorCode::UnknownJob => StatusCode::NOT_FOUND,
+            ErrorCode::InvalidInput | ErrorCode::NoSolution => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::Panicked | ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            ErrorCode::QueueFull => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::Cancelled => StatusCode::CONFLICT,
+        }
+    }
+}
+
+/// The body of every error the API returns, with the status its `code` maps
+/// to.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// The day the request was for, when it got far enough to know.
+    pub day: Option<u8>,
+    /// The part that failed, when the error belongs to one part.
+    pub part: Option<u8>,
+    /// Where in the input parsing failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Box<Diagnostic>>,
+    /// The ID of the request that failed, as in its `x-request-id` header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        ApiError {
+            code,
+            message: message.into(),
+            day: None,
+            part: None,
+            location: None,
+            request_id: request_id::current(),
+        }
+    }
+
+    pub fn for_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// An error from solving `part` (or parsing the input for it) of `day`,
+    /// with parse errors pointed at their place in `input`.
+    pub fn solving(err: &SolutionError, day: u8, part: Option<SolutionPart>, input: &str) -> Self {
+        let code = match err {
+            SolutionError::UnknownProblem(_) => ErrorCode::UnknownProblem,
+            SolutionError::DataParsingError(_) => ErrorCode::InvalidInput,
+            SolutionError::NoPossibleSolution => ErrorCode::NoSolution,
+            SolutionError::Panicked(_) => ErrorCode::Panicked,
+            SolutionError::Cancelled => ErrorCode::Cancelled,
+            SolutionError::FileLoadError { .. }
+            | SolutionError::MissingInput { .. }
+            | SolutionError::FileSaveError { .. }
+            | SolutionError::InputReadError(_)
+            | SolutionError::InvalidFile { .. } => ErrorCode::Internal,
+        };
+        let location = match err {
+            SolutionError::DataParsingError(err) => Some(Box::new(Diagnostic::new(err, input))),
+            _ => None,
+        };
+        ApiError {
+            code,
+            message: err.to_string(),
+            day: Some(day),
+            part: part.map(SolutionPart::number),
+            location,
+            request_id: request_id::current(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.code.status(), Json(self)).into_response()
+    }
+}
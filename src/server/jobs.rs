@@ -0,0 +1,294 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{Path, State},
+    http::{header::LOCATION, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+use tokio::sync::Semaphore;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::util::{cancel::CancelToken, PartSelection};
+
+use super::{
+    answer,
+    error::{ApiError, ErrorCode},
+    nanos,
+    request::SolveRequest,
+    request_id, AppState, Problem, ProblemOutput, SolveTimings,
+};
+
+/// How long a finished job is kept for its client to collect.
+const JOB_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for a worker.
+    Queued,
+    Running,
+    /// Solved; the job has the answer.
+    Done,
+    /// The job has the error that stopped it.
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    fn is_finished(self) -> bool {
+        !matches!(self, JobStatus::Queued | JobStatus::Running)
+    }
+}
+
+struct Job {
+    day: u8,
+    parts: Vec<u8>,
+    status: JobStatus,
+    token: CancelToken,
+    created: Instant,
+    started: Option<Instant>,
+    finished: Option<Instant>,
+    output: Option<ProblemOutput>,
+    error: Option<ApiError>,
+    solve_timings: Option<SolveTimings>,
+}
+
+/// A job as the jobs endpoints return it. A finished job has the same
+/// `answer` or `parts` as a `/solve` response, or the `error` it failed with.
+#[derive(Serialize)]
+pub(super) struct JobView {
+    id: String,
+    day: u8,
+    parts: Vec<u8>,
+    status: JobStatus,
+    #[serde(flatten)]
+    output: Option<ProblemOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ApiError>,
+    timings: JobTimings,
+}
+
+/// How long a job has spent so far, in nanoseconds.
+#[derive(Serialize)]
+struct JobTimings {
+    /// Waiting for a worker.
+    queued_ns: u64,
+    /// On a worker, including reading the cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    running_ns: Option<u64>,
+    /// What the solver reported, unless the answers came from the cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solve: Option<SolveTimings>,
+}
+
+impl Job {
+    fn view(&self, id: &str) -> JobView {
+        let now = Instant::now();
+        let queued_until = self.started.or(self.finished).unwrap_or(now);
+        JobView {
+            id: id.to_string(),
+            day: self.day,
+            parts: self.parts.clone(),
+            status: self.status,
+            output: self.output.clone(),
+            error: self.error.clone(),
+            timings: JobTimings {
+                queued_ns: nanos(queued_until - self.created),
+                running_ns: self
+                    .started
+                    .map(|started| nanos(self.finished.unwrap_or(now) - started)),
+                solve: self.solve_timings.clone(),
+            },
+        }
+    }
+}
+
+/// How many jobs are waiting for a worker and how many are running.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct JobCounts {
+    pub(super) queued: usize,
+    pub(super) running: usize,
+}
+
+/// The jobs the server knows of, and the workers that run them. At most as
+/// many jobs run at once as there are workers; the rest wait their turn, up
+/// to `max_queued` of them.
+pub(super) struct Jobs {
+    jobs: Mutex<HashMap<String, Job>>,
+    workers: Semaphore,
+    max_queued: usize,
+}
+
+impl Jobs {
+    pub(super) fn new(workers: usize, max_queued: usize) -> Self {
+        Jobs {
+            jobs: Mutex::default(),
+            workers: Semaphore::new(workers.max(1)),
+            max_queued,
+        }
+    }
+
+    /// Adds a queued job for `parts` of `day` under a new ID, dropping the
+    /// jobs that finished too long ago. Fails if the queue is full.
+    fn add(&self, day: u8, parts: PartSelection, token: CancelToken) -> Result<String, ApiError> {
+        let id = Uuid::new_v4().to_string();
+        let job = Job {
+            day,
+            parts: parts.parts().iter().map(|part| part.number()).collect(),
+            status: JobStatus::Queued,
+            token,
+            created: Instant::now(),
+            started: None,
+            finished: None,
+            output: None,
+            error: None,
+            solve_timings: None,
+        };
+
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.retain(|_, job| {
+            job.finished
+                .is_none_or(|finished| finished.elapsed() < JOB_RETENTION)
+        });
+        // Queued jobs that a free worker is about to take aren't waiting, so
+        // only the ones beyond the free workers count against the queue.
+        let queued = jobs
+            .values()
+            .filter(|job| job.status == JobStatus::Queued)
+            .count();
+        let free_workers = self.workers.available_permits();
+        if queued >= free_workers + self.max_queued {
+            return Err(ApiError::new(
+                ErrorCode::QueueFull,
+                format!(
+                    "{} jobs are already waiting for a worker",
+                    queued - free_workers
+                ),
+            )
+            .for_day(day));
+        }
+        jobs.insert(id.clone(), job);
+        Ok(id)
+    }
+
+    pub(super) fn counts(&self) -> JobCounts {
+        let mut counts = JobCounts::default();
+        for job in self.jobs.lock().unwrap().values() {
+            match job.status {
+                JobStatus::Queued => counts.queued += 1,
+                JobStatus::Running => counts.running += 1,
+                _ => {}
+            }
+        }
+        counts
+    }
+
+    fn view(&self, id: &str) -> Option<JobView> {
+        self.jobs.lock().unwrap().get(id).map(|job| job.view(id))
+    }
+
+    fn update(&self, id: &str, change: impl FnOnce(&mut Job)) {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(id) {
+            change(job);
+        }
+    }
+}
+
+fn unknown_job(id: &str) -> ApiError {
+    ApiError::new(ErrorCode::UnknownJob, format!("there is no job `{}`", id))
+}
+
+/// `POST /jobs`: queues a request in any of the formats `/solve` takes and
+/// returns the new job right away. The job runs as part of this request, so
+/// its errors and logs carry this request's ID.
+pub(super) async fn create_job(
+    State(state): State<AppState>,
+    request: SolveRequest,
+) -> Result<impl IntoResponse, ApiError> {
+    let problem = Problem::new(&state, request)?;
+    let token = CancelToken::default();
+    let id = state.jobs.add(problem.day, problem.parts, token.clone())?;
+    let job = run_job(state.clone(), id.clone(), problem, token);
+    tokio::spawn(request_id::scope(request_id::current(), job).in_current_span());
+
+    let view = state.jobs.view(&id).ok_or_else(|| unknown_job(&id))?;
+    let location = format!("/jobs/{}", id);
+    Ok((StatusCode::ACCEPTED, [(LOCATION, location)], Json(view)))
+}
+
+async fn run_job(state: AppState, id: String, problem: Problem, token: CancelToken) {
+    let _worker = state
+        .jobs
+        .workers
+        .acquire()
+        .await
+        .expect("the worker pool is never closed");
+    // Checked under the same lock `cancel_job` takes, so a job cancelled
+    // while it waited for a worker is never marked running.
+    let mut cancelled = false;
+    state.jobs.update(&id, |job| {
+        if job.status == JobStatus::Cancelled {
+            cancelled = true;
+            return;
+        }
+        job.status = JobStatus::Running;
+        job.started = Some(Instant::now());
+    });
+    if cancelled {
+        return;
+    }
+
+    let answered = answer(&state, problem, token, None).await;
+    state.jobs.update(&id, |job| {
+        if job.status == JobStatus::Cancelled {
+            return;
+        }
+        job.finished = Some(Instant::now());
+        match answered {
+            Ok(answered) => {
+                job.status = JobStatus::Done;
+                job.output = Some(answered.output);
+                job.solve_timings = answered.timings;
+            }
+            Err(err) => {
+                job.status = JobStatus::Failed;
+                job.error = Some(err);
+            }
+        }
+    });
+}
+
+/// `GET /jobs/{id}`
+pub(super) async fn get_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<JobView>, ApiError> {
+    state
+        .jobs
+        .view(&id)
+        .map(Json)
+        .ok_or_else(|| unknown_job(&id))
+}
+
+/// `DELETE /jobs/{id}`: cancels a job that hasn't finished. A running solver
+/// stops at its next checkpoint.
+pub(super) async fn cancel_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<JobView>, ApiError> {
+    state.jobs.update(&id, |job| {
+        if !job.status.is_finished() {
+            job.status = JobStatus::Cancelled;
+            job.finished = Some(Instant::now());
+            job.token.cancel();
+        }
+    });
+    get_job(State(state), Path(id)).await
+}
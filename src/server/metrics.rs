@@ -0,0 +1,319 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header::CONTENT_TYPE,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::solutions::Solved;
+
+use super::{jobs::JobCounts, AppState};
+
+/// Upper bounds of the buckets that parse and solve times fall into, in
+/// seconds: from day 1's microseconds to day 5's minutes.
+const SECONDS_BUCKETS: &[f64] = &[
+    0.0001, 0.001, 0.01, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0,
+];
+
+/// Upper bounds of the buckets that input sizes fall into, in bytes, up to
+/// the largest body the server takes.
+const INPUT_BYTES_BUCKETS: &[f64] = &[
+    1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0, 16777216.0,
+];
+
+/// Counts of observations that fell at or below each bucket's bound.
+#[derive(Debug, Clone)]
+struct Histogram {
+    bounds: &'static [f64],
+    /// How many observations fell in each bucket, and not in the ones below.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Histogram {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        if let Some(bucket) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.counts[bucket] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Writes the `_bucket`, `_sum` and `_count` lines of `name` with
+    /// `labels`, which are either empty or end with a comma.
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let mut cumulative = 0;
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            cumulative += count;
+            let _ = writeln!(
+                out,
+                "{}_bucket{{{}le=\"{}\"}} {}",
+                name, labels, bound, cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{}_bucket{{{}le=\"+Inf\"}} {}",
+            name, labels, self.count
+        );
+        let labels = match labels.trim_end_matches(',') {
+            "" => String::new(),
+            labels => format!("{{{}}}", labels),
+        };
+        let _ = writeln!(out, "{}_sum{} {}", name, labels, self.sum);
+        let _ = writeln!(out, "{}_count{} {}", name, labels, self.count);
+    }
+}
+
+/// Counts of what the server has seen since it started, served by
+/// `GET /metrics` in Prometheus' text format.
+#[derive(Debug)]
+pub(super) struct Metrics {
+    /// Keyed by method, route and status.
+    requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    /// Keyed by day.
+    parse_seconds: Mutex<BTreeMap<u8, Histogram>>,
+    /// Keyed by day and part.
+    solve_seconds: Mutex<BTreeMap<(u8, u8), Histogram>>,
+    input_bytes: Mutex<Histogram>,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    panics: AtomicU64,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Metrics {
+            requests: Mutex::default(),
+            parse_seconds: Mutex::default(),
+            solve_seconds: Mutex::default(),
+            input_bytes: Mutex::new(Histogram::new(INPUT_BYTES_BUCKETS)),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+            panics: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Metrics {
+    fn record_request(&self, method: &str, route: &str, status: u16) {
+        let mut requests = self.requests.lock().unwrap();
+        *requests
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default() += 1;
+    }
+
+    pub(super) fn record_input(&self, bytes: usize) {
+        self.input_bytes.lock().unwrap().observe(bytes as f64);
+    }
+
+    pub(super) fn record_cache(&self, hit: bool) {
+        let counter = if hit {
+            &self.cache_hits
+        } else {
+            &self.cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records how long day `day` took to parse and to solve each part.
+    pub(super) fn record_solved(&self, day: u8, solved: &Solved) {
+        let seconds = |duration: Duration| duration.as_secs_f64();
+        self.parse_seconds
+            .lock()
+            .unwrap()
+            .entry(day)
+            .or_insert_with(|| Histogram::new(SECONDS_BUCKETS))
+            .observe(seconds(solved.parse_time));
+
+        let mut solve_seconds = self.solve_seconds.lock().unwrap();
+        for part_solved in &solved.parts {
+            solve_seconds
+                .entry((day, part_solved.part.number()))
+                .or_insert_with(|| Histogram::new(SECONDS_BUCKETS))
+                .observe(seconds(part_solved.solve_time));
+        }
+    }
+
+    pub(super) fn record_panic(&self) {
+        self.panics.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// How many times a solver has panicked.
+    pub(super) fn panics(&self) -> u64 {
+        self.panics.load(Ordering::Relaxed)
+    }
+
+    /// Every metric in Prometheus' text format, with `jobs` as the jobs that
+    /// are in flight now.
+    pub(super) fn render(&self, jobs: JobCounts) -> String {
+        fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        }
+
+        let mut out = String::new();
+
+        let name = "aoc_http_requests_total";
+        header(
+            &mut out,
+            name,
+            "counter",
+            "Requests handled, by route and status.",
+        );
+        for ((method, route, status), count) in self.requests.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "{}{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                name, method, route, status, count
+            );
+        }
+
+        let name = "aoc_parse_duration_seconds";
+        header(
+            &mut out,
+            name,
+            "histogram",
+            "Time spent parsing inputs, by day.",
+        );
+        for (day, histogram) in self.parse_seconds.lock().unwrap().iter() {
+            histogram.render(&mut out, name, &format!("day=\"{}\",", day));
+        }
+
+        let name = "aoc_solve_duration_seconds";
+        header(
+            &mut out,
+            name,
+            "histogram",
+            "Time spent solving parts, by day and part.",
+        );
+        for ((day, part), histogram) in self.solve_seconds.lock().unwrap().iter() {
+            let labels = format!("day=\"{}\",part=\"{}\",", day, part);
+            histogram.render(&mut out, name, &labels);
+        }
+
+        let name = "aoc_input_bytes";
+        header(
+            &mut out,
+            name,
+            "histogram",
+            "Sizes of the inputs asked about.",
+        );
+        self.input_bytes.lock().unwrap().render(&mut out, name, "");
+
+        let hits = self.cache_hits.load(Ordering::Relaxed);
+        let misses = self.cache_misses.load(Ordering::Relaxed);
+        for (name, help, count) in [
+            (
+                "aoc_cache_hits_total",
+                "Answers served from the cache.",
+                hits,
+            ),
+            (
+                "aoc_cache_misses_total",
+                "Answers that had to be solved.",
+                misses,
+            ),
+        ] {
+            header(&mut out, name, "counter", help);
+            let _ = writeln!(out, "{} {}", name, count);
+        }
+        let name = "aoc_cache_hit_ratio";
+        header(
+            &mut out,
+            name,
+            "gauge",
+            "Share of answers served from the cache since the server started.",
+        );
+        let ratio = hits as f64 / (hits + misses).max(1) as f64;
+        let _ = writeln!(out, "{} {}", name, ratio);
+
+        let name = "aoc_jobs_in_flight";
+        header(
+            &mut out,
+            name,
+            "gauge",
+            "Jobs that haven't finished, by status.",
+        );
+        let _ = writeln!(out, "{}{{status=\"queued\"}} {}", name, jobs.queued);
+        let _ = writeln!(out, "{}{{status=\"running\"}} {}", name, jobs.running);
+
+        let name = "aoc_solver_panics_total";
+        header(&mut out, name, "counter", "Times a solver panicked.");
+        let _ = writeln!(out, "{} {}", name, self.panics());
+
+        out
+    }
+}
+
+/// Counts every request by its method, route and status. Requests that match
+/// no route are counted under `unmatched`, so that made-up paths don't each
+/// get their own series.
+pub(super) async fn track_requests(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", MatchedPath::as_str)
+        .to_string();
+    let response = next.run(request).await;
+    metrics.record_request(&method, &route, response.status().as_u16());
+    response
+}
+
+/// `GET /metrics`
+pub(super) async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(state.jobs.counts()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let mut histogram = Histogram::new(&[1.0, 10.0]);
+        for value in [0.5, 2.0, 3.0, 20.0] {
+            histogram.observe(value);
+        }
+
+        let mut out = String::new();
+        histogram.render(&mut out, "sizes", "day=\"5\",");
+
+        assert_eq!(
+            out,
+            "sizes_bucket{day=\"5\",le=\"1\"} 1\n\
+             sizes_bucket{day=\"5\",le=\"10\"} 3\n\
+             sizes_bucket{day=\"5\",le=\"+Inf\"} 4\n\
+             sizes_sum{day=\"5\"} 25.5\n\
+             sizes_count{day=\"5\"} 4\n"
+        );
+    }
+}
@@ -0,0 +1,452 @@
+mod batch;
+pub mod cache;
+pub mod error;
+pub mod jobs;
+mod metrics;
+mod problems;
+mod request;
+pub mod request_id;
+mod stream;
+
+use std::{fmt, net::Ipv4Addr, num::NonZeroUsize, str::FromStr, sync::Arc, thread, time::Duration};
+
+use axum::{
+    extract::{DefaultBodyLimit, State},
+    middleware,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::SolutionError,
+    solutions::{Solved, Solver, SOLUTIONS},
+    util::{
+        cancel::CancelToken,
+        progress::{self, ProgressSink},
+        PartSelection, SolutionPart,
+    },
+};
+
+use cache::{AnswerCache, CacheStatus, InputHash};
+use error::{ApiError, ErrorCode};
+use jobs::Jobs;
+use metrics::Metrics;
+use request::SolveRequest;
+
+const DEFAULT_SERVER_ADDR: &str = "0.0.0.0";
+const DEFAULT_SERVER_PORT: u16 = 3000_u16;
+
+/// How the server treats the requests it takes.
+#[derive(Debug, Clone, Copy)]
+pub struct ServerOptions {
+    /// How long a `/solve` request may spend solving before it fails with
+    /// 504 and its solver is told to stop.
+    pub timeout: Duration,
+    /// How many answers to cache. 0 turns the cache off.
+    pub cache_size: usize,
+    /// How long a cached answer is served before it is solved again.
+    pub cache_ttl: Duration,
+    /// How many jobs from `/jobs` run at once.
+    pub job_workers: usize,
+    /// How many jobs may wait for a worker before `/jobs` turns new ones away
+    /// with 503.
+    pub max_queued_jobs: usize,
+    /// How many items of one `/solve/batch` request are solved at once.
+    pub batch_concurrency: usize,
+    /// Whether to serve the `/admin` routes. They aren't authenticated, so
+    /// they are off unless asked for.
+    pub admin: bool,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        let cpus = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        ServerOptions {
+            timeout: Duration::from_secs(30),
+            cache_size: 256,
+            cache_ttl: Duration::from_secs(60 * 60),
+            job_workers: cpus,
+            max_queued_jobs: 64,
+            batch_concurrency: cpus,
+            admin: false,
+        }
+    }
+}
+
+/// Serves the solutions over HTTP on `addr:port` until the process stops,
+/// listening on 0.0.0.0:3000 unless told otherwise.
+pub async fn serve(addr: Option<Ipv4Addr>, port: Option<u16>, options: ServerOptions) {
+    let server_listen_addr = addr.unwrap_or(Ipv4Addr::from_str(DEFAULT_SERVER_ADDR).unwrap());
+    let server_listen_port = port.unwrap_or(DEFAULT_SERVER_PORT);
+
+    // initialize tracing
+    tracing_subscriber::fmt::init();
+
+    let listener =
+        tokio::net::TcpListener::bind(format!("{}:{}", server_listen_addr, server_listen_port))
+            .await
+            .unwrap();
+    axum::serve(listener, app_with(SOLUTIONS, options))
+        .await
+        .unwrap();
+}
+
+/// Puzzle inputs can be larger than axum's default 2MB body limit.
+const MAX_INPUT_BYTES: usize = 16 * 1024 * 1024;
+
+/// The routes `serve` serves, for running the API some other way.
+pub fn app() -> Router {
+    app_with(SOLUTIONS, ServerOptions::default())
+}
+
+/// `app` serving `solvers` instead of the registered days, with `options`.
+pub fn app_with(solvers: &'static [&'static dyn Solver], options: ServerOptions) -> Router {
+    router(AppState {
+        solvers,
+        options,
+        cache: Arc::new(AnswerCache::new(options.cache_size, options.cache_ttl)),
+        jobs: Arc::new(Jobs::new(options.job_workers, options.max_queued_jobs)),
+        metrics: Arc::default(),
+    })
+}
+
+fn router(state: AppState) -> Router {
+    let mut router = Router::new()
+        .route("/", get(root))
+        .route("/problems", get(problems::list_problems))
+        .route("/problems/:day", get(problems::get_problem))
+        .route("/solve", post(solve))
+        .route("/solve/batch", post(batch::solve_batch))
+        .route("/solve/stream", post(stream::solve_stream))
+        .route("/jobs", post(jobs::create_job))
+        .route("/jobs/:id", get(jobs::get_job).delete(jobs::cancel_job))
+        .route("/metrics", get(metrics::get_metrics));
+    if state.options.admin {
+        router = router.route("/admin/cache", delete(cache::clear_cache));
+    }
+    router
+        .layer(DefaultBodyLimit::max(MAX_INPUT_BYTES))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state.metrics),
+            metrics::track_requests,
+        ))
+        .layer(middleware::from_fn(request_id::assign))
+        .with_state(state)
+}
+
+/// What every handler can reach.
+#[derive(Clone)]
+struct AppState {
+    solvers: &'static [&'static dyn Solver],
+    options: ServerOptions,
+    cache: Arc<AnswerCache>,
+    jobs: Arc<Jobs>,
+    metrics: Arc<Metrics>,
+}
+
+impl AppState {
+    fn find(&self, day: u8) -> Option<&'static dyn Solver> {
+        self.solvers
+            .iter()
+            .copied()
+            .find(|solver| solver.day() == day)
+    }
+
+    /// Logs and counts the panics in `solved`. They are still reported to the
+    /// client like any other error.
+    fn record_panics(&self, day: u8, solved: &Result<Solved, SolutionError>) {
+        let errors: Vec<_> = match solved {
+            Ok(solved) => solved
+                .parts
+                .iter()
+                .filter_map(|part_solved| {
+                    Some((Some(part_solved.part), part_solved.answer.as_ref().err()?))
+                })
+                .collect(),
+            Err(err) => vec![(None, err)],
+        };
+        for (part, err) in errors {
+            if let SolutionError::Panicked(message) = err {
+                self.metrics.record_panic();
+                tracing::error!(
+                    day,
+                    part = part.map(SolutionPart::number),
+                    total_panics = self.metrics.panics(),
+                    "solver panicked: {}",
+                    message
+                );
+            }
+        }
+    }
+}
+
+async fn root() -> &'static str {
+    "Hello, World!"
+}
+
+fn get_solution_parts(part: &PartInput) -> Option<PartSelection> {
+    match part {
+        PartInput::Number(1) => Some(PartSelection::One),
+        PartInput::Number(2) => Some(PartSelection::Two),
+        PartInput::Number(_) => None,
+        // Query strings and form fields only carry text, so "1" and "2" are
+        // accepted as names too.
+        PartInput::Name(name) => PartSelection::from_str(name, true).ok(),
+    }
+}
+
+/// The part that errors from solving `parts` belong to, when only one was
+/// asked for. A single part's parse error is reported as that part's.
+fn requested_part(parts: PartSelection) -> Option<SolutionPart> {
+    match parts {
+        PartSelection::Both => None,
+        _ => parts.parts().first().copied(),
+    }
+}
+
+/// A request for parts of a day the server has a solver for.
+struct Problem {
+    day: u8,
+    parts: PartSelection,
+    solver: &'static dyn Solver,
+    input: String,
+}
+
+impl Problem {
+    /// Checks that `request` is for a part and day that can be solved.
+    fn new(state: &AppState, request: SolveRequest) -> Result<Self, ApiError> {
+        let day = request.problem;
+        let Some(parts) = get_solution_parts(&request.part) else {
+            return Err(ApiError::new(
+                ErrorCode::InvalidPart,
+                format!("part {} is not 1, 2 or \"both\"", request.part),
+            )
+            .for_day(day));
+        };
+        let Some(solver) = state.find(day) else {
+            let err = SolutionError::UnknownProblem(day);
+            let part = requested_part(parts);
+            return Err(ApiError::solving(&err, day, part, &request.data));
+        };
+        Ok(Problem {
+            day,
+            parts,
+            solver,
+            input: request.data,
+        })
+    }
+}
+
+/// What answering a `Problem` came to.
+struct Answered {
+    output: ProblemOutput,
+    cache: CacheStatus,
+    /// `None` when the answers came from the cache.
+    timings: Option<SolveTimings>,
+}
+
+/// How long the solver spent parsing and on each part, in nanoseconds.
+#[derive(Debug, Clone, Serialize)]
+struct SolveTimings {
+    parse_ns: u64,
+    parts: Vec<PartTiming>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PartTiming {
+    part: u8,
+    solve_ns: u64,
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+impl SolveTimings {
+    fn new(solved: &Solved) -> Self {
+        SolveTimings {
+            parse_ns: nanos(solved.parse_time),
+            parts: solved
+                .parts
+                .iter()
+                .map(|part_solved| PartTiming {
+                    part: part_solved.part.number(),
+                    solve_ns: nanos(part_solved.solve_time),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Answers `problem` from the cache, or else solves it and caches the
+/// answers. Solvers are CPU-bound, so they run on the blocking pool to keep
+/// the runtime free for other requests. The solver runs under `token` and
+/// stops at its next checkpoint once the token is cancelled. Its progress
+/// goes to `progress`, if given.
+async fn answer(
+    state: &AppState,
+    problem: Problem,
+    token: CancelToken,
+    progress: Option<ProgressSink>,
+) -> Result<Answered, ApiError> {
+    let Problem {
+        day,
+        parts,
+        solver,
+        input,
+    } = problem;
+    state.metrics.record_input(input.len());
+    let input_hash = InputHash::new(&input);
+    let cached: Option<Vec<_>> = parts
+        .parts()
+        .iter()
+        .map(|&part| Some((part, Ok(state.cache.get(day, part, input_hash)?))))
+        .collect();
+    state.metrics.record_cache(cached.is_some());
+    if let Some(answers) = cached {
+        return Ok(Answered {
+            output: problem_output(day, parts, answers, &input)?,
+            cache: CacheStatus::Hit,
+            timings: None,
+        });
+    }
+
+    let task = tokio::task::spawn_blocking(move || {
+        let solve = || solver.solve_parts(&input, parts.parts());
+        let solved = token.scope(|| match progress {
+            Some(progress) => progress::scope(progress, solve),
+            None => solve(),
+        });
+        (input, solved)
+    });
+    let (input, solved) = task
+        .await
+        .map_err(|err| ApiError::new(ErrorCode::Internal, err.to_string()).for_day(day))?;
+    state.record_panics(day, &solved);
+    let solved =
+        solved.map_err(|err| ApiError::solving(&err, day, requested_part(parts), &input))?;
+
+    state.metrics.record_solved(day, &solved);
+    let timings = SolveTimings::new(&solved);
+    let answers: Vec<_> = solved
+        .parts
+        .into_iter()
+        .map(|part_solved| (part_solved.part, part_solved.answer))
+        .collect();
+    for (part, answer) in &answers {
+        if let Ok(answer) = answer {
+            state.cache.insert(day, *part, input_hash, answer.clone());
+        }
+    }
+    Ok(Answered {
+        output: problem_output(day, parts, answers, &input)?,
+        cache: CacheStatus::Miss,
+        timings: Some(timings),
+    })
+}
+
+async fn solve(
+    State(state): State<AppState>,
+    request: SolveRequest,
+) -> Result<(CacheStatus, Json<ProblemOutput>), ApiError> {
+    let problem = Problem::new(&state, request)?;
+    let day = problem.day;
+
+    // If this future is dropped, because the client went away or the timeout
+    // below fired, the solver is cancelled at its next checkpoint.
+    let token = CancelToken::default();
+    let _cancel = token.cancel_on_drop();
+    match tokio::time::timeout(state.options.timeout, answer(&state, problem, token, None)).await {
+        Ok(answered) => {
+            let answered = answered?;
+            Ok((answered.cache, Json(answered.output)))
+        }
+        Err(_) => Err(timed_out(day, state.options.timeout)),
+    }
+}
+
+fn timed_out(day: u8, timeout: Duration) -> ApiError {
+    ApiError::new(
+        ErrorCode::Timeout,
+        format!("solving took longer than {:?}", timeout),
+    )
+    .for_day(day)
+}
+
+/// The response to a request for `parts` of `day`, given the answer or error
+/// for each part.
+fn problem_output(
+    day: u8,
+    parts: PartSelection,
+    mut answers: Vec<(SolutionPart, Result<String, SolutionError>)>,
+    input: &str,
+) -> Result<ProblemOutput, ApiError> {
+    if parts != PartSelection::Both {
+        let (part, answer) = answers.remove(0);
+        return match answer {
+            Ok(answer) => Ok(ProblemOutput {
+                answer: Some(answer),
+                parts: None,
+            }),
+            Err(err) => Err(ApiError::solving(&err, day, Some(part), input)),
+        };
+    }
+
+    // Both parts share one parse, so once it succeeds each part reports its
+    // own answer or error and the request as a whole succeeds.
+    let part_outputs = answers
+        .into_iter()
+        .map(|(part, answer)| match answer {
+            Ok(answer) => PartOutput {
+                part: part.number(),
+                answer: Some(answer),
+                error: None,
+            },
+            Err(err) => PartOutput {
+                part: part.number(),
+                answer: None,
+                error: Some(ApiError::solving(&err, day, Some(part), input)),
+            },
+        })
+        .collect();
+    Ok(ProblemOutput {
+        answer: None,
+        parts: Some(part_outputs),
+    })
+}
+
+/// A part is either a number or a name: `"1"`, `"2"` or `"both"`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PartInput {
+    Number(u8),
+    Name(String),
+}
+
+impl fmt::Display for PartInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartInput::Number(number) => write!(f, "{}", number),
+            PartInput::Name(name) => write!(f, "\"{}\"", name),
+        }
+    }
+}
+
+/// A successful `/solve` response.
+#[derive(Clone, Serialize)]
+struct ProblemOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    /// One entry per part when `"both"` parts were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parts: Option<Vec<PartOutput>>,
+}
+
+#[derive(Clone, Serialize)]
+struct PartOutput {
+    part: u8,
+    answer: Option<String>,
+    error: Option<ApiError>,
+}
@@ -0,0 +1,92 @@
+use axum::{
+    extract::{rejection::PathRejection, Path, State},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{solutions::Solver, util::PartSelection};
+
+use super::{
+    error::{ApiError, ErrorCode},
+    AppState,
+};
+
+/// A registered day, as listed by `GET /problems`.
+#[derive(Serialize)]
+pub(super) struct ProblemSummary {
+    day: u8,
+    title: &'static str,
+    parts: Vec<u8>,
+    has_examples: bool,
+    input_format: &'static str,
+}
+
+/// A registered day with its examples, as returned by `GET /problems/{day}`.
+#[derive(Serialize)]
+pub(super) struct ProblemDetails {
+    #[serde(flatten)]
+    summary: ProblemSummary,
+    examples: Vec<ExampleOutput>,
+}
+
+#[derive(Serialize)]
+struct ExampleOutput {
+    input: &'static str,
+    part_one: Option<&'static str>,
+    part_two: Option<&'static str>,
+}
+
+impl ProblemSummary {
+    fn new(solver: &dyn Solver) -> Self {
+        ProblemSummary {
+            day: solver.day(),
+            title: solver.title(),
+            parts: PartSelection::Both
+                .parts()
+                .iter()
+                .map(|part| part.number())
+                .collect(),
+            has_examples: !solver.examples().is_empty(),
+            input_format: solver.input_format(),
+        }
+    }
+}
+
+pub(super) async fn list_problems(State(state): State<AppState>) -> Json<Vec<ProblemSummary>> {
+    Json(
+        state
+            .solvers
+            .iter()
+            .map(|solver| ProblemSummary::new(*solver))
+            .collect(),
+    )
+}
+
+pub(super) async fn get_problem(
+    State(state): State<AppState>,
+    day: Result<Path<u8>, PathRejection>,
+) -> Result<Json<ProblemDetails>, ApiError> {
+    let Path(day) =
+        day.map_err(|rejection| ApiError::new(ErrorCode::BadRequest, rejection.body_text()))?;
+    let Some(solver) = state.find(day) else {
+        return Err(ApiError::new(
+            ErrorCode::UnknownProblem,
+            format!("day {} has no solution", day),
+        )
+        .for_day(day));
+    };
+
+    let examples = solver
+        .examples()
+        .iter()
+        .map(|example| ExampleOutput {
+            input: example.input,
+            part_one: example.part_one,
+            part_two: example.part_two,
+        })
+        .collect();
+    Ok(Json(ProblemDetails {
+        summary: ProblemSummary::new(solver),
+        examples,
+    }))
+}
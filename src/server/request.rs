@@ -0,0 +1,223 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, Multipart, Query, Request},
+    http::{header::CONTENT_TYPE, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{
+    error::{ApiError, ErrorCode},
+    PartInput,
+};
+
+/// A `/solve` request, read from any of the body formats it accepts:
+///
+/// - `application/json`: `{"problem": 2, "part": 1, "data": "..."}`
+/// - `text/plain`: the input as the body, with `?problem=2&part=1`
+/// - `multipart/form-data`: the input as a `data` field or file upload, with
+///   `problem` and `part` as fields or query parameters
+///
+/// Clients that can't send newlines can set `backtick_newlines` (a JSON field
+/// or query parameter) to have every backtick in the input read as one.
+pub(super) struct SolveRequest {
+    pub(super) problem: u8,
+    pub(super) part: PartInput,
+    pub(super) data: String,
+}
+
+#[derive(Deserialize)]
+struct JsonRequest {
+    problem: u8,
+    part: PartInput,
+    data: String,
+    #[serde(default)]
+    backtick_newlines: bool,
+}
+
+#[derive(Deserialize)]
+struct SolveParams {
+    problem: Option<u8>,
+    part: Option<PartInput>,
+    #[serde(default)]
+    backtick_newlines: bool,
+}
+
+type Rejection = ApiError;
+
+/// A request that couldn't be read, with the code for `status`.
+fn reject(status: StatusCode, message: impl Into<String>) -> Rejection {
+    let code = match status {
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => ErrorCode::UnsupportedMediaType,
+        _ => ErrorCode::BadRequest,
+    };
+    ApiError::new(code, message)
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequest<S> for SolveRequest {
+    type Rejection = Rejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        match mime.as_str() {
+            "application/json" => {
+                let Json(request) = Json::<JsonRequest>::from_request(req, state)
+                    .await
+                    .map_err(|rejection| reject(rejection.status(), rejection.body_text()))?;
+                Ok(request.into())
+            }
+            "text/plain" => {
+                let params = params(&req)?;
+                let data = String::from_request(req, state)
+                    .await
+                    .map_err(|rejection| reject(rejection.status(), rejection.body_text()))?;
+                let (problem, part) = required(params.problem, params.part)?;
+                Ok(SolveRequest::new(
+                    problem,
+                    part,
+                    data,
+                    params.backtick_newlines,
+                ))
+            }
+            "multipart/form-data" => {
+                let params = params(&req)?;
+                let multipart = Multipart::from_request(req, state)
+                    .await
+                    .map_err(|rejection| reject(rejection.status(), rejection.body_text()))?;
+                from_multipart(multipart, params).await
+            }
+            _ => Err(reject(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!(
+                    "Unsupported content type `{}`: send application/json, text/plain or multipart/form-data.",
+                    content_type
+                ),
+            )),
+        }
+    }
+}
+
+impl From<JsonRequest> for SolveRequest {
+    fn from(request: JsonRequest) -> Self {
+        SolveRequest::new(
+            request.problem,
+            request.part,
+            request.data,
+            request.backtick_newlines,
+        )
+    }
+}
+
+/// A `/solve/batch` request: a JSON array of `/solve` JSON bodies. Each item
+/// is read on its own, so one that can't be read fails alone.
+pub(super) struct BatchRequest(pub(super) Vec<Result<SolveRequest, Rejection>>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequest<S> for BatchRequest {
+    type Rejection = Rejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(items) = Json::<Vec<Value>>::from_request(req, state)
+            .await
+            .map_err(|rejection| reject(rejection.status(), rejection.body_text()))?;
+        let requests = items
+            .into_iter()
+            .map(|item| {
+                serde_json::from_value::<JsonRequest>(item)
+                    .map(SolveRequest::from)
+                    .map_err(|err| reject(StatusCode::BAD_REQUEST, err.to_string()))
+            })
+            .collect();
+        Ok(BatchRequest(requests))
+    }
+}
+
+impl SolveRequest {
+    fn new(problem: u8, part: PartInput, data: String, backtick_newlines: bool) -> Self {
+        let data = if backtick_newlines {
+            data.replace('`', "\n")
+        } else {
+            data
+        };
+        SolveRequest {
+            problem,
+            part,
+            data,
+        }
+    }
+}
+
+fn params(req: &Request) -> Result<SolveParams, Rejection> {
+    Query::<SolveParams>::try_from_uri(req.uri())
+        .map(|Query(params)| params)
+        .map_err(|rejection| reject(rejection.status(), rejection.body_text()))
+}
+
+fn required(problem: Option<u8>, part: Option<PartInput>) -> Result<(u8, PartInput), Rejection> {
+    match (problem, part) {
+        (Some(problem), Some(part)) => Ok((problem, part)),
+        _ => Err(reject(
+            StatusCode::BAD_REQUEST,
+            "Both `problem` and `part` are required.",
+        )),
+    }
+}
+
+async fn from_multipart(
+    mut multipart: Multipart,
+    params: SolveParams,
+) -> Result<SolveRequest, Rejection> {
+    let mut problem = params.problem;
+    let mut part = params.part;
+    let mut backtick_newlines = params.backtick_newlines;
+    let mut data = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|err| reject(err.status(), err.body_text()))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        let value = field
+            .text()
+            .await
+            .map_err(|err| reject(err.status(), err.body_text()))?;
+        match name.as_str() {
+            "data" => data = Some(value),
+            "problem" => match value.trim().parse() {
+                Ok(day) => problem = Some(day),
+                Err(_) => {
+                    return Err(reject(
+                        StatusCode::BAD_REQUEST,
+                        format!("`{}` is not a day number.", value),
+                    ))
+                }
+            },
+            "part" => part = Some(PartInput::Name(value.trim().to_string())),
+            "backtick_newlines" => backtick_newlines = value.trim() == "true",
+            _ => {}
+        }
+    }
+
+    let (problem, part) = required(problem, part)?;
+    let Some(data) = data else {
+        return Err(reject(
+            StatusCode::BAD_REQUEST,
+            "The input is missing: send it as a `data` field or file.",
+        ));
+    };
+    Ok(SolveRequest::new(problem, part, data, backtick_newlines))
+}
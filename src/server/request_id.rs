@@ -0,0 +1,62 @@
+use std::future::Future;
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// The header a request ID is read from and sent back in.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// IDs from clients longer than this are replaced rather than echoed.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static CURRENT: String;
+}
+
+/// Gives every request an ID, taken from its `x-request-id` header or made up,
+/// and sends it back in the same header. Handlers run with it as the current
+/// request's ID and inside a span that logs it.
+pub(super) async fn assign(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+        .map(String::from)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!(
+        "request",
+        id = %request_id,
+        method = %request.method(),
+        uri = %request.uri(),
+    );
+    let mut response = CURRENT
+        .scope(request_id.clone(), next.run(request))
+        .instrument(span)
+        .await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Runs `future` as part of the request `request_id`, for work a request
+/// hands to another task.
+pub(super) async fn scope<F: Future>(request_id: Option<String>, future: F) -> F::Output {
+    match request_id {
+        Some(request_id) => CURRENT.scope(request_id, future).await,
+        None => future.await,
+    }
+}
+
+/// The ID of the request being handled, if any.
+pub fn current() -> Option<String> {
+    CURRENT.try_with(String::clone).ok()
+}
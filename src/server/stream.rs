@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+    Error,
+};
+use tokio::sync::{mpsc, watch};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+use tracing::Instrument;
+
+use crate::util::{cancel::CancelToken, progress::ProgressSink};
+
+use super::{answer, error::ApiError, request::SolveRequest, request_id, AppState, Problem};
+
+/// How many events may wait for a slow client before progress is skipped.
+const EVENT_BUFFER: usize = 16;
+
+/// `POST /solve/stream`: solves a request in any of the formats `/solve`
+/// takes, streaming the solver's `progress` as server-sent events and then
+/// either an `answer` event with the `/solve` response or an `error` event.
+/// Unlike `/solve` there is no timeout: the solver is cancelled when the
+/// client disconnects. Like a job, the stream keeps this request's ID.
+pub(super) async fn solve_stream(
+    State(state): State<AppState>,
+    request: SolveRequest,
+) -> Result<Sse<impl Stream<Item = Result<Event, Error>>>, ApiError> {
+    let problem = Problem::new(&state, request)?;
+    let (events, receiver) = mpsc::channel(EVENT_BUFFER);
+    let send = send_events(state, problem, events);
+    tokio::spawn(request_id::scope(request_id::current(), send).in_current_span());
+    Ok(Sse::new(ReceiverStream::new(receiver)).keep_alive(KeepAlive::default()))
+}
+
+async fn send_events(
+    state: AppState,
+    problem: Problem,
+    events: mpsc::Sender<Result<Event, Error>>,
+) {
+    // Returning early, once the client is gone, cancels the solver.
+    let token = CancelToken::default();
+    let _cancel = token.cancel_on_drop();
+
+    // Only the latest progress matters, so a slow client skips the rest.
+    let (progress_sender, mut progress) = watch::channel(None);
+    let sink: ProgressSink = Arc::new(move |report| {
+        progress_sender.send_replace(Some(*report));
+    });
+
+    let answered = answer(&state, problem, token, Some(sink));
+    tokio::pin!(answered);
+    let answered = loop {
+        tokio::select! {
+            // Progress first, so the last report goes out before the answer.
+            biased;
+            Ok(()) = progress.changed() => {
+                let Some(report) = *progress.borrow_and_update() else {
+                    continue;
+                };
+                if events.send(Event::default().event("progress").json_data(report)).await.is_err() {
+                    return;
+                }
+            }
+            () = events.closed() => return,
+            answered = &mut answered => break answered,
+        }
+    };
+
+    let event = match answered {
+        Ok(answered) => Event::default().event("answer").json_data(answered.output),
+        Err(err) => Event::default().event("error").json_data(err),
+    };
+    let _ = events.send(event).await;
+}
@@ -1,4 +1,8 @@
-use crate::{error::SolutionError, util::SolutionPart};
+use crate::{
+    error::{ParseError, SolutionError},
+    solutions::{Example, Solution},
+    util::{cancel::checkpoint, parse_numbers, progress, text::paragraphs},
+};
 use arr_macro::arr;
 
 // The correct (optimized) approach for this problem is actually a kinda interesting
@@ -7,16 +11,18 @@ use arr_macro::arr;
 //
 // Create an interval tree
 
+/// Source values `start..end` map to themselves plus `transform_factor`.
 #[derive(Clone, Copy)]
-struct AlmanacRange {
-    start: i64,
-    end: i64,
-    transform_factor: i64,
+pub struct AlmanacRange {
+    pub start: i64,
+    pub end: i64,
+    pub transform_factor: i64,
 }
 
+/// One of the almanac's maps; values outside every range map to themselves.
 #[derive(Clone, Default)]
-struct AlmanacMap {
-    ranges: Vec<AlmanacRange>,
+pub struct AlmanacMap {
+    pub ranges: Vec<AlmanacRange>,
 }
 
 impl AlmanacRange {
@@ -25,22 +31,54 @@ impl AlmanacRange {
     }
 }
 
-struct DataPayload {
-    seeds: Vec<i64>,
-    maps: [AlmanacMap; 7],
+pub struct DataPayload {
+    pub seeds: Vec<i64>,
+    /// The maps in the order a seed goes through them, seed-to-soil first.
+    pub maps: [AlmanacMap; 7],
 }
 
-impl From<&str> for AlmanacRange {
-    fn from(value: &str) -> Self {
-        let mut values = value.split(' ');
-        let dest_start = values.nth(0).unwrap().parse::<i64>().unwrap();
-        let source_start = values.nth(0).unwrap().parse::<i64>().unwrap();
-        let length = values.nth(0).unwrap().parse::<i64>().unwrap();
-        AlmanacRange {
+const DAY: u8 = 5;
+/// How many seeds to map between checks for cancellation and progress
+/// reports.
+const CHECKPOINT_INTERVAL: usize = 1 << 16;
+
+/// The almanac's map headers, in the order a seed is pushed through them.
+const MAP_TITLES: [&str; 7] = [
+    "seed-to-soil map",
+    "soil-to-fertilizer map",
+    "fertilizer-to-water map",
+    "water-to-light map",
+    "light-to-temperature map",
+    "temperature-to-humidity map",
+    "humidity-to-location map",
+];
+
+impl AlmanacRange {
+    fn parse(line_number: usize, line: &str) -> Result<Self, ParseError> {
+        let values = parse_numbers::<i64>(DAY, line_number, line, line)?;
+        let [dest_start, source_start, length] = values[..] else {
+            return Err(ParseError::new(
+                DAY,
+                line_number,
+                line,
+                line,
+                "`<destination start> <source start> <length>`",
+            ));
+        };
+        // `parse_numbers` read one value from each of these.
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let out_of_range = |token: &str| {
+            ParseError::new(DAY, line_number, line, token, "a range that fits in an i64")
+        };
+        Ok(AlmanacRange {
             start: source_start,
-            end: source_start + length,
-            transform_factor: dest_start - source_start,
-        }
+            end: source_start
+                .checked_add(length)
+                .ok_or_else(|| out_of_range(tokens[2]))?,
+            transform_factor: dest_start
+                .checked_sub(source_start)
+                .ok_or_else(|| out_of_range(tokens[0]))?,
+        })
     }
 }
 
@@ -51,7 +89,7 @@ impl AlmanacMap {
                 return range.transform_factor;
             }
         }
-        return 0;
+        0
     }
 
     fn builder() -> Self {
@@ -59,113 +97,138 @@ impl AlmanacMap {
     }
 }
 
-fn extract_seeds(seeds_str: &str, solution_part: &SolutionPart, container: &mut Vec<i64>) {
-    let seeds_vec: Vec<i64> = seeds_str
-        .trim()
-        .split(' ')
-        .map(|seed| seed.parse::<i64>().unwrap())
-        .collect();
-    match solution_part {
-        SolutionPart::PartOne => {
-            for seed in seeds_vec {
-                container.push(seed)
-            }
-        }
-        SolutionPart::PartTwo => {
-            for pair in seeds_vec.chunks_exact(2) {
-                let start = pair[0];
-                let range = pair[1];
-                for i in start..range {
-                    container.push(i);
-                }
-            }
-        }
-    }
-}
-
-fn parse_input_data(
-    problem_data: &String,
-    solution_part: SolutionPart,
-) -> Result<DataPayload, SolutionError> {
+fn parse_input_data(problem_data: &str) -> Result<DataPayload, SolutionError> {
     let mut seeds: Vec<i64> = vec![];
     let mut maps: [AlmanacMap; 7] = arr![AlmanacMap::builder(); 7];
 
-    let chunks = problem_data.split("\n\n");
-
-    for chunk in chunks {
-        let mut lines = chunk.lines();
-        let title_line = lines.nth(0).unwrap();
-        if !title_line.contains(':') {
-            return Err(SolutionError::DataParsingError);
-        }
-        if let Some(title) = title_line.split(':').nth(0) {
-            match title {
-                "seeds" => {
-                    if let Some(seed_str) = title_line.split(':').nth(1) {
-                        extract_seeds(seed_str, &solution_part, &mut seeds);
-                    }
-                    continue;
-                }
-                "seed-to-soil map" => {
-                    for range_str in lines {
-                        maps[0].ranges.push(AlmanacRange::from(range_str));
-                    }
-                }
-                "soil-to-fertilizer map" => {
-                    for range_str in lines {
-                        maps[1].ranges.push(AlmanacRange::from(range_str));
-                    }
-                }
-                "fertilizer-to-water map" => {
-                    for range_str in lines {
-                        maps[2].ranges.push(AlmanacRange::from(range_str));
-                    }
-                }
-                "water-to-light map" => {
-                    for range_str in lines {
-                        maps[3].ranges.push(AlmanacRange::from(range_str));
-                    }
-                }
-                "light-to-temperature map" => {
-                    for range_str in lines {
-                        maps[4].ranges.push(AlmanacRange::from(range_str));
-                    }
-                }
-                "temperature-to-humidity map" => {
-                    for range_str in lines {
-                        maps[5].ranges.push(AlmanacRange::from(range_str));
-                    }
-                }
-                "humidity-to-location map" => {
-                    for range_str in lines {
-                        maps[6].ranges.push(AlmanacRange::from(range_str));
-                    }
-                }
-                _ => return Err(SolutionError::DataParsingError),
+    // Each section is a header line, followed by the ranges for a map.
+    for section in paragraphs(problem_data) {
+        let (line_number, header) = section[0];
+        let Some((title, rest)) = header.split_once(':') else {
+            return Err(ParseError::new(
+                DAY,
+                line_number,
+                header,
+                header,
+                "a section header such as `seeds:` or `seed-to-soil map:`",
+            )
+            .into());
+        };
+        if title == "seeds" {
+            seeds = parse_numbers(DAY, line_number, header, rest)?;
+            if let Some((line_number, line)) = section.get(1) {
+                return Err(ParseError::new(
+                    DAY,
+                    *line_number,
+                    line,
+                    line,
+                    "a blank line after the seeds",
+                )
+                .into());
             }
-        } else {
-            return Err(SolutionError::DataParsingError);
+            continue;
+        }
+        let Some(map) = MAP_TITLES.iter().position(|map_title| *map_title == title) else {
+            return Err(ParseError::new(
+                DAY,
+                line_number,
+                header,
+                title,
+                "`seeds` or one of the almanac map names",
+            )
+            .into());
+        };
+        for (line_number, line) in &section[1..] {
+            maps[map]
+                .ranges
+                .push(AlmanacRange::parse(*line_number, line)?);
         }
     }
     Ok(DataPayload { seeds, maps })
 }
 
-pub fn run(problem_data: String, solution_part: SolutionPart) -> Result<String, SolutionError> {
-    let payload = parse_input_data(&problem_data, solution_part)?;
+pub struct DayFive;
+
+impl Solution for DayFive {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+    const INPUT_FORMAT: &'static str =
+        "A `seeds: <n> ...` line, then the seven `<source>-to-<destination> map:` \
+         sections of `<destination start> <source start> <length>` lines, separated \
+         by blank lines.";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("../../../inputs/2023/day05.example1.txt"),
+        part_one: Some("35"),
+        part_two: Some("46"),
+    }];
+
+    type Input = DataPayload;
+
+    fn parse(problem_data: &str) -> Result<Self::Input, SolutionError> {
+        parse_input_data(problem_data)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolutionError> {
+        let seeds = input.seeds.iter().copied();
+        Ok(solve(seeds, input.seeds.len() as u64, &input.maps)?.to_string())
+    }
 
-    Ok(solve(payload).to_string())
+    fn part_two(input: &Self::Input) -> Result<String, SolutionError> {
+        // Part two reads the seed line as `start length` pairs.
+        let seeds = input
+            .seeds
+            .chunks_exact(2)
+            .flat_map(|pair| pair[0]..pair[0] + pair[1]);
+        let seed_count = input
+            .seeds
+            .chunks_exact(2)
+            .map(|pair| pair[1].max(0) as u64)
+            .sum();
+        Ok(solve(seeds, seed_count, &input.maps)?.to_string())
+    }
 }
 
-fn solve(payload: DataPayload) -> i64 {
+/// The lowest location of any of the `seed_count` `seeds`.
+fn solve(
+    seeds: impl Iterator<Item = i64>,
+    seed_count: u64,
+    maps: &[AlmanacMap],
+) -> Result<i64, SolutionError> {
     let mut minimum_location = i64::MAX;
 
-    for (_index, seed) in payload.seeds.iter().enumerate() {
-        let mut pointer_value = seed.clone();
-        for map in payload.maps.as_slice() {
+    for (index, seed) in seeds.enumerate() {
+        // Part two runs through billions of seeds.
+        if index % CHECKPOINT_INTERVAL == 0 {
+            checkpoint()?;
+            progress::report(index as u64, seed_count);
+        }
+        let mut pointer_value = seed;
+        for map in maps {
             pointer_value += map.get_transform(pointer_value);
         }
         minimum_location = i64::min(pointer_value, minimum_location);
     }
 
-    minimum_location
+    progress::report(seed_count, seed_count);
+    Ok(minimum_location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overflowing_range_is_located() {
+        let Err(err) = AlmanacRange::parse(4, "1 9223372036854775800 100") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (4, 23));
+        assert_eq!(err.snippet, "100");
+
+        let Err(err) = AlmanacRange::parse(4, "-9223372036854775800 100 1") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(err.column, 1);
+        assert_eq!(err.snippet, "-9223372036854775800");
+    }
 }
@@ -1,34 +1,44 @@
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     fmt::Display,
+    io::BufRead,
 };
 
-use crate::{error::SolutionError, util::SolutionPart};
+use crate::{
+    error::{ParseError, SolutionError},
+    solutions::{Example, Solution},
+    util::{
+        cancel::checkpoint,
+        parse_numbers,
+        text::{for_each_line, lines},
+    },
+};
 
-struct ScratchCard {
-    winning_numbers: HashSet<u32>,
-    contained_numbers: Vec<u32>,
-    num_winning_numbers: u32,
-    points: u32,
+pub struct ScratchCard {
+    pub winning_numbers: HashSet<u32>,
+    pub contained_numbers: Vec<u32>,
+    /// How many of `contained_numbers` are winning numbers.
+    pub num_winning_numbers: u32,
+    pub points: u32,
 }
 
-impl From<&str> for ScratchCard {
-    fn from(value: &str) -> Self {
-        let mut winning_numbers: HashSet<u32> = HashSet::new();
-        let mut contained_numbers: Vec<u32> = vec![];
+const DAY: u8 = 4;
+/// How many cards to process between checks for cancellation.
+const CHECKPOINT_INTERVAL: i32 = 1 << 16;
+const CARD_FORMAT: &str = "`Card <n>: <winning numbers> | <numbers you have>`";
 
-        let card_numbers = value.split_once(':').unwrap().1.split_once('|').unwrap();
+impl ScratchCard {
+    fn parse(line_number: usize, line: &str) -> Result<Self, ParseError> {
+        let card_numbers = line
+            .split_once(':')
+            .and_then(|(_, numbers)| numbers.split_once('|'))
+            .ok_or_else(|| ParseError::new(DAY, line_number, line, line, CARD_FORMAT))?;
 
-        for winning_number in card_numbers.0.trim().split(' ') {
-            if let Ok(num) = winning_number.trim().parse::<u32>() {
-                winning_numbers.insert(num);
-            }
-        }
-        for contained_number in card_numbers.1.trim().split(' ') {
-            if let Ok(num) = contained_number.trim().parse::<u32>() {
-                contained_numbers.push(num)
-            }
-        }
+        let winning_numbers: HashSet<u32> =
+            parse_numbers::<u32>(DAY, line_number, line, card_numbers.0)?
+                .into_iter()
+                .collect();
+        let contained_numbers: Vec<u32> = parse_numbers(DAY, line_number, line, card_numbers.1)?;
 
         let num_winning_numbers = contained_numbers
             .iter()
@@ -37,15 +47,23 @@ impl From<&str> for ScratchCard {
 
         let points: u32 = match num_winning_numbers {
             0 => 0,
-            _ => 2_u32.pow(num_winning_numbers as u32 - 1),
+            _ => 2_u32.checked_pow(num_winning_numbers - 1).ok_or_else(|| {
+                ParseError::new(
+                    DAY,
+                    line_number,
+                    line,
+                    card_numbers.1.trim(),
+                    "at most 32 winning numbers on a card",
+                )
+            })?,
         };
 
-        ScratchCard {
+        Ok(ScratchCard {
             winning_numbers,
             contained_numbers,
             num_winning_numbers,
             points,
-        }
+        })
     }
 }
 
@@ -71,20 +89,48 @@ impl Display for ScratchCard {
     }
 }
 
-pub fn run(problem_data: String, solution_part: SolutionPart) -> Result<String, SolutionError> {
-    let mut cards: Vec<ScratchCard> = vec![];
-    for line in problem_data.split('\n') {
-        let card = ScratchCard::from(line);
-        cards.push(card);
+pub struct DayFour;
+
+impl Solution for DayFour {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Scratchcards";
+    const INPUT_FORMAT: &'static str =
+        "One card per line: `Card <n>: <winning numbers> | <numbers you have>`.";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("../../../inputs/2023/day04.example1.txt"),
+        part_one: Some("13"),
+        part_two: Some("30"),
+    }];
+
+    type Input = Vec<ScratchCard>;
+
+    fn parse(problem_data: &str) -> Result<Self::Input, SolutionError> {
+        let mut cards = vec![];
+        for (line_number, line) in lines(problem_data) {
+            cards.push(ScratchCard::parse(line_number, line)?);
+        }
+        Ok(cards)
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, SolutionError> {
+        let mut cards = vec![];
+        for_each_line(reader, |line_number, line| {
+            cards.push(ScratchCard::parse(line_number, line)?);
+            Ok(())
+        })?;
+        Ok(cards)
     }
 
-    match solution_part {
-        SolutionPart::PartOne => Ok(part_one_solution(cards).to_string()),
-        SolutionPart::PartTwo => Ok(part_two_solution(cards).to_string()),
+    fn part_one(input: &Self::Input) -> Result<String, SolutionError> {
+        Ok(part_one_solution(input).to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolutionError> {
+        Ok(part_two_solution(input)?.to_string())
     }
 }
 
-fn part_two_solution(cards: Vec<ScratchCard>) -> i32 {
+fn part_two_solution(cards: &[ScratchCard]) -> Result<i32, SolutionError> {
     let card_map: HashMap<usize, &ScratchCard> = cards
         .iter()
         .enumerate()
@@ -93,15 +139,44 @@ fn part_two_solution(cards: Vec<ScratchCard>) -> i32 {
     let mut card_deque: VecDeque<(usize, &ScratchCard)> = cards.iter().enumerate().collect();
     let mut answer = 0;
     while let Some((index, card)) = card_deque.pop_front() {
+        // Every copy won is processed on its own, so this runs once per card
+        // in the final count.
+        if answer % CHECKPOINT_INTERVAL == 0 {
+            checkpoint()?;
+        }
         answer += 1;
         for i in 1..card.num_winning_numbers + 1 {
             let new_card_index = index + i as usize;
-            card_deque.push_back((new_card_index, card_map[&new_card_index]))
+            // Cards never win copies of cards past the end of the table.
+            let new_card = card_map
+                .get(&new_card_index)
+                .ok_or(SolutionError::NoPossibleSolution)?;
+            card_deque.push_back((new_card_index, new_card))
         }
     }
-    answer
+    Ok(answer)
 }
 
-fn part_one_solution(cards: Vec<ScratchCard>) -> i32 {
+fn part_one_solution(cards: &[ScratchCard]) -> i32 {
     cards.iter().map(|card| card.points).sum::<u32>() as i32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_too_many_winning_numbers_is_located() {
+        let numbers: Vec<String> = (1..=33).map(|number| number.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let line = format!("Card 1: {} | {}", numbers, numbers);
+
+        let Err(err) = ScratchCard::parse(2, &line) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, line.find('|').unwrap() + 3);
+        assert_eq!(err.snippet, numbers);
+    }
+}
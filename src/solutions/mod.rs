@@ -4,3 +4,307 @@ pub mod one;
 pub mod six;
 pub mod three;
 pub mod two;
+
+use std::{
+    io::BufRead,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::SolutionError,
+    util::{progress, SolutionPart},
+};
+
+/// A single day's puzzle. Implement this for a day's module and add it to
+/// `SOLUTIONS` below; the CLI and the server both look days up from there.
+///
+/// `parse` turns the raw puzzle text into the day's model once, and both parts
+/// are then solved from that model without touching the raw text again.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// A short description of what the day's input looks like.
+    const INPUT_FORMAT: &'static str;
+    /// The worked examples from the puzzle text. Every registered day's
+    /// examples are checked by `cargo test`.
+    const EXAMPLES: &'static [Example] = &[];
+
+    type Input;
+
+    fn parse(problem_data: &str) -> Result<Self::Input, SolutionError>;
+    /// Parses the input as it is read from `reader`. By default the whole
+    /// input is read into a string for `parse`; days whose input is a list of
+    /// lines override this to parse one line at a time.
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, SolutionError> {
+        let mut problem_data = String::new();
+        reader
+            .read_to_string(&mut problem_data)
+            .map_err(SolutionError::InputReadError)?;
+        Self::parse(&problem_data)
+    }
+    fn part_one(input: &Self::Input) -> Result<String, SolutionError>;
+    fn part_two(input: &Self::Input) -> Result<String, SolutionError>;
+}
+
+/// An example input from the puzzle text with the answers it gives. A part
+/// the puzzle text has no answer for is left as `None`.
+pub struct Example {
+    pub input: &'static str,
+    pub part_one: Option<&'static str>,
+    pub part_two: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: SolutionPart) -> Option<&'static str> {
+        match part {
+            SolutionPart::PartOne => self.part_one,
+            SolutionPart::PartTwo => self.part_two,
+        }
+    }
+}
+
+/// Object-safe view of a `Solution` so that every day can live in one table.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn input_format(&self) -> &'static str;
+    fn examples(&self) -> &'static [Example];
+    /// Parses `problem_data` once and solves each of `parts` from the same
+    /// model. Only a parse failure fails the whole call; every part keeps its
+    /// own result.
+    ///
+    /// A panic in the parser or in a part is reported as
+    /// `SolutionError::Panicked` for that step instead of unwinding further.
+    fn solve_parts(
+        &self,
+        problem_data: &str,
+        parts: &[SolutionPart],
+    ) -> Result<Solved, SolutionError>;
+    /// `solve_parts` for an input that is parsed as it is read from `reader`.
+    fn solve_parts_from_reader(
+        &self,
+        reader: &mut dyn BufRead,
+        parts: &[SolutionPart],
+    ) -> Result<Solved, SolutionError>;
+}
+
+/// The answers for one parse of a day's input, with how long each step took.
+pub struct Solved {
+    pub parse_time: Duration,
+    pub parts: Vec<PartSolved>,
+}
+
+pub struct PartSolved {
+    pub part: SolutionPart,
+    pub answer: Result<String, SolutionError>,
+    pub solve_time: Duration,
+}
+
+/// Runs `step`, turning a panic inside it into `SolutionError::Panicked`.
+fn catch_panic<T>(step: impl FnOnce() -> Result<T, SolutionError>) -> Result<T, SolutionError> {
+    match panic::catch_unwind(AssertUnwindSafe(step)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = if let Some(message) = payload.downcast_ref::<&str>() {
+                message.to_string()
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.clone()
+            } else {
+                String::from("unknown panic")
+            };
+            Err(SolutionError::Panicked(message))
+        }
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn input_format(&self) -> &'static str {
+        S::INPUT_FORMAT
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn solve_parts(
+        &self,
+        problem_data: &str,
+        parts: &[SolutionPart],
+    ) -> Result<Solved, SolutionError> {
+        solve_parsed::<S>(|| S::parse(problem_data), parts)
+    }
+
+    fn solve_parts_from_reader(
+        &self,
+        reader: &mut dyn BufRead,
+        parts: &[SolutionPart],
+    ) -> Result<Solved, SolutionError> {
+        solve_parsed::<S>(|| S::parse_reader(reader), parts)
+    }
+}
+
+/// Times `parse` and then each of `parts` solved from what it returns.
+fn solve_parsed<S: Solution>(
+    parse: impl FnOnce() -> Result<S::Input, SolutionError>,
+    parts: &[SolutionPart],
+) -> Result<Solved, SolutionError> {
+    let parse_start = Instant::now();
+    progress::start(None);
+    let input = catch_panic(parse)?;
+    let parse_time = parse_start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let solve_start = Instant::now();
+            progress::start(Some(part));
+            let answer = catch_panic(|| match part {
+                SolutionPart::PartOne => S::part_one(&input),
+                SolutionPart::PartTwo => S::part_two(&input),
+            });
+            PartSolved {
+                part,
+                answer,
+                solve_time: solve_start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Solved { parse_time, parts })
+}
+
+pub static SOLUTIONS: &[&dyn Solver] = &[
+    &one::DayOne,
+    &two::DayTwo,
+    &three::DayThree,
+    &four::DayFour,
+    &five::DayFive,
+    &six::DaySix,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Solver> {
+    SOLUTIONS.iter().copied().find(|solver| solver.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::PartSelection;
+
+    #[test]
+    fn test_every_day_has_examples() {
+        for solver in SOLUTIONS {
+            assert!(
+                !solver.examples().is_empty(),
+                "day {} has no examples",
+                solver.day()
+            );
+        }
+    }
+
+    #[test]
+    fn test_registered_examples() {
+        let mut failures = vec![];
+        for solver in SOLUTIONS {
+            for (index, example) in solver.examples().iter().enumerate() {
+                let parts: Vec<SolutionPart> = [SolutionPart::PartOne, SolutionPart::PartTwo]
+                    .into_iter()
+                    .filter(|part| example.expected(*part).is_some())
+                    .collect();
+                let solved = match solver.solve_parts(example.input, &parts) {
+                    Ok(solved) => solved,
+                    Err(err) => {
+                        failures.push(format!(
+                            "day {} example {}: {}",
+                            solver.day(),
+                            index + 1,
+                            err
+                        ));
+                        continue;
+                    }
+                };
+                for part_solved in solved.parts {
+                    let expected = example.expected(part_solved.part).unwrap();
+                    let answer = match part_solved.answer {
+                        Ok(answer) => answer,
+                        Err(err) => err.to_string(),
+                    };
+                    if answer != expected {
+                        failures.push(format!(
+                            "day {} example {} part {}: expected {}, got {}",
+                            solver.day(),
+                            index + 1,
+                            part_solved.part.number(),
+                            expected,
+                            answer
+                        ));
+                    }
+                }
+            }
+        }
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    fn answers(solved: Result<Solved, SolutionError>) -> Vec<String> {
+        match solved {
+            Ok(solved) => solved
+                .parts
+                .into_iter()
+                .map(|part_solved| match part_solved.answer {
+                    Ok(answer) => answer,
+                    Err(err) => err.to_string(),
+                })
+                .collect(),
+            Err(err) => vec![err.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_reader_parse_matches_str_parse() {
+        let parts = PartSelection::Both.parts();
+        for solver in SOLUTIONS {
+            for example in solver.examples() {
+                let mut reader = example.input.as_bytes();
+                assert_eq!(
+                    answers(solver.solve_parts_from_reader(&mut reader, parts)),
+                    answers(solver.solve_parts(example.input, parts)),
+                    "day {}",
+                    solver.day()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_examples_as_saved_on_windows() {
+        let parts = PartSelection::Both.parts();
+        for solver in SOLUTIONS {
+            for example in solver.examples() {
+                let saved = format!("\u{feff}{}\r\n\r\n", example.input.replace('\n', "\r\n"));
+                let expected = answers(solver.solve_parts(example.input, parts));
+
+                assert_eq!(
+                    answers(solver.solve_parts(&saved, parts)),
+                    expected,
+                    "day {}",
+                    solver.day()
+                );
+                assert_eq!(
+                    answers(solver.solve_parts_from_reader(&mut saved.as_bytes(), parts)),
+                    expected,
+                    "day {}",
+                    solver.day()
+                );
+            }
+        }
+    }
+}
@@ -1,2 +1,2 @@
 pub mod solution;
-pub use solution::run;
+pub use solution::DayOne;
@@ -1,4 +1,12 @@
-use crate::{error::SolutionError, util::SolutionPart};
+use std::io::BufRead;
+
+use crate::{
+    error::{ParseError, SolutionError},
+    solutions::{Example, Solution},
+    util::text::{for_each_line, lines},
+};
+
+const DAY: u8 = 1;
 
 const NUMBER_WORDS: &[&str] = &[
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
@@ -48,18 +56,76 @@ fn extract_number(s: &str, reverse: bool, accept_num_words: bool) -> Result<u32,
     Err(SolutionError::NoPossibleSolution)
 }
 
-pub fn run(problem_data: String, part: SolutionPart) -> Result<String, SolutionError> {
+fn calibration_sum(scribbles: &[String], accept_num_words: bool) -> Result<u32, SolutionError> {
     let mut calibration_sum: u32 = 0;
 
-    let accept_num_words = match part {
-        SolutionPart::PartOne => false,
-        SolutionPart::PartTwo => true,
-    };
-
-    for scribble in problem_data.split('\n') {
+    for scribble in scribbles {
         let first_digit: u32 = extract_number(scribble, false, accept_num_words)?;
         let second_digit: u32 = extract_number(scribble, true, accept_num_words)?;
         calibration_sum += (first_digit * 10) + second_digit;
     }
-    Ok(calibration_sum.to_string())
+    Ok(calibration_sum)
+}
+
+fn parse_scribble(line_number: usize, scribble: &str) -> Result<String, ParseError> {
+    // The digit scan above indexes the line byte by byte.
+    if let Some((offset, c)) = scribble.char_indices().find(|(_, c)| !c.is_ascii()) {
+        let snippet = &scribble[offset..offset + c.len_utf8()];
+        return Err(ParseError::new(
+            DAY,
+            line_number,
+            scribble,
+            snippet,
+            "ASCII letters and digits",
+        ));
+    }
+    Ok(scribble.to_string())
+}
+
+pub struct DayOne;
+
+impl Solution for DayOne {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Trebuchet?!";
+    const INPUT_FORMAT: &'static str =
+        "One calibration line per row, of ASCII letters and digits such as `two1nine`.";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: include_str!("../../../inputs/2023/day01.example1.txt"),
+            part_one: Some("142"),
+            part_two: None,
+        },
+        Example {
+            input: include_str!("../../../inputs/2023/day01.example2.txt"),
+            part_one: None,
+            part_two: Some("281"),
+        },
+    ];
+
+    type Input = Vec<String>;
+
+    fn parse(problem_data: &str) -> Result<Self::Input, SolutionError> {
+        let mut scribbles = vec![];
+        for (line_number, scribble) in lines(problem_data) {
+            scribbles.push(parse_scribble(line_number, scribble)?);
+        }
+        Ok(scribbles)
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, SolutionError> {
+        let mut scribbles = vec![];
+        for_each_line(reader, |line_number, scribble| {
+            scribbles.push(parse_scribble(line_number, scribble)?);
+            Ok(())
+        })?;
+        Ok(scribbles)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolutionError> {
+        Ok(calibration_sum(input, false)?.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolutionError> {
+        Ok(calibration_sum(input, true)?.to_string())
+    }
 }
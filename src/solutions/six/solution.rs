@@ -1,80 +1,123 @@
-use crate::{error::SolutionError, util::SolutionPart};
+use crate::{
+    error::{ParseError, SolutionError},
+    solutions::{Example, Solution},
+    util::{cancel::checkpoint, parse_numbers, text::lines},
+};
 
-struct DataPayload {
-    races: Vec<(i64, i64)>,
+pub struct DataPayload {
+    /// Each race's time and record distance.
+    pub races: Vec<(i64, i64)>,
 }
 
-fn parse_input_data(
-    problem_data: String,
-    solution_part: SolutionPart,
-) -> Result<DataPayload, SolutionError> {
-    let mut races = vec![];
-
-    let (time_str, dist_str) = problem_data.split_once('\n').unwrap();
-    let time_strs: Vec<&str> = time_str
-        .split_once(':')
-        .unwrap()
-        .1
-        .trim()
-        .split(' ')
-        .filter(|c| c.len() > 0)
-        .collect();
-    let dist_strs: Vec<&str> = dist_str
-        .split_once(':')
-        .unwrap()
-        .1
-        .trim()
-        .split(' ')
-        .filter(|c| c.len() > 0)
-        .collect();
-
-    let times: Vec<i64> = match solution_part {
-        SolutionPart::PartOne => time_strs
-            .iter()
-            .map(|n_str| n_str.parse::<i64>().unwrap())
-            .collect(),
-        SolutionPart::PartTwo => vec![time_strs.join("").to_string().parse::<i64>().unwrap()],
-    };
-
-    let distances: Vec<i64> = match solution_part {
-        SolutionPart::PartOne => dist_strs
-            .iter()
-            .map(|n_str| n_str.parse::<i64>().unwrap())
-            .collect(),
-        SolutionPart::PartTwo => vec![dist_strs.join("").to_string().parse::<i64>().unwrap()],
-    };
-
-    for i in 0..times.len() {
-        races.push((
-            times.get(i).unwrap().clone(),
-            distances.get(i).unwrap().clone(),
-        ));
+const DAY: u8 = 6;
+/// How many hold times to try between checks for cancellation.
+const CHECKPOINT_INTERVAL: i64 = 1 << 16;
+
+/// Reads the numbers following `label` on the given line of the input.
+fn parse_row(line_number: usize, line: &str, label: &str) -> Result<Vec<i64>, ParseError> {
+    match line.strip_prefix(label) {
+        Some(numbers) => parse_numbers(DAY, line_number, line, numbers),
+        None => Err(ParseError::new(
+            DAY,
+            line_number,
+            line,
+            line,
+            format!("`{} <n> <n> ...`", label),
+        )),
+    }
+}
+
+fn parse_input_data(problem_data: &str) -> Result<DataPayload, SolutionError> {
+    let mut rows = lines(problem_data);
+    let (_, time_str) = rows.next().unwrap_or((1, ""));
+    let (_, dist_str) = rows.next().unwrap_or((2, ""));
+    if let Some((line_number, extra)) = rows.next() {
+        return Err(ParseError::new(DAY, line_number, extra, extra, "the end of the input").into());
     }
 
+    let times = parse_row(1, time_str, "Time:")?;
+    let distances = parse_row(2, dist_str, "Distance:")?;
+    if times.len() != distances.len() {
+        return Err(ParseError::new(
+            DAY,
+            2,
+            dist_str,
+            dist_str,
+            format!("{} distances, one for each race time", times.len()),
+        )
+        .into());
+    }
+
+    let races = times.into_iter().zip(distances).collect();
     Ok(DataPayload { races })
 }
 
+/// Part two reads each line as one number with the spaces between the
+/// columns removed, so `7  15   30` becomes `71530`.
+fn kerned(values: impl Iterator<Item = i64>) -> Result<i64, SolutionError> {
+    values
+        .map(|value| value.to_string())
+        .collect::<String>()
+        .parse::<i64>()
+        .map_err(|_| SolutionError::NoPossibleSolution)
+}
+
 fn total_distance(time_held: i64, total_time: i64) -> i64 {
     time_held * (total_time - time_held)
 }
 
-fn solve(payload: &DataPayload) -> i64 {
+/// The product of how many hold times beat each race's record. Distances are
+/// symmetric around half the race time, so if no hold time up to that wins,
+/// none does.
+fn solve(races: &[(i64, i64)]) -> Result<i64, SolutionError> {
     let mut options_product = 1;
-    for race in &payload.races {
+    for race in races {
         let time = race.0;
         let distance = race.1;
 
-        let mut time_to_beat = 0;
-        while total_distance(time_to_beat, time) < distance {
-            time_to_beat += 1;
+        let mut time_to_beat = None;
+        for time_held in 0..=time / 2 {
+            // Part two's single race can take billions of tries.
+            if time_held % CHECKPOINT_INTERVAL == 0 {
+                checkpoint()?;
+            }
+            if total_distance(time_held, time) > distance {
+                time_to_beat = Some(time_held);
+                break;
+            }
         }
+        let time_to_beat = time_to_beat.ok_or(SolutionError::NoPossibleSolution)?;
         options_product *= time - ((2 * time_to_beat) - 1);
     }
-    options_product
+    Ok(options_product)
 }
 
-pub fn run(problem_data: String, solution_part: SolutionPart) -> Result<String, SolutionError> {
-    let payload = parse_input_data(problem_data, solution_part)?;
+pub struct DaySix;
 
-    Ok(solve(&payload).to_string())
+impl Solution for DaySix {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Wait For It";
+    const INPUT_FORMAT: &'static str =
+        "Two lines: `Time: <n> ...` and `Distance: <n> ...`, one number per race.";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("../../../inputs/2023/day06.example1.txt"),
+        part_one: Some("288"),
+        part_two: Some("71503"),
+    }];
+
+    type Input = DataPayload;
+
+    fn parse(problem_data: &str) -> Result<Self::Input, SolutionError> {
+        parse_input_data(problem_data)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolutionError> {
+        Ok(solve(&input.races)?.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolutionError> {
+        let time = kerned(input.races.iter().map(|race| race.0))?;
+        let distance = kerned(input.races.iter().map(|race| race.1))?;
+        Ok(solve(&[(time, distance)])?.to_string())
+    }
 }
@@ -1,34 +1,26 @@
 use std::{collections::HashSet, fmt, ops::Index};
 
-use crate::{error::SolutionError, util::SolutionPart};
+use crate::{
+    error::{ParseError, SolutionError},
+    solutions::{Example, Solution},
+    util::text::lines,
+};
+
+const DAY: u8 = 3;
 const NUMBERS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
 const IGNORED_CELL: char = '.';
 
-struct EngineSchematic {
-    height: usize,
-    width: usize,
-    data: Vec<Vec<char>>,
+/// The engine schematic as a grid of characters, `data[row][col]`.
+pub struct EngineSchematic {
+    pub height: usize,
+    pub width: usize,
+    pub data: Vec<Vec<char>>,
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct SchematicPoint {
-    row: usize,
-    col: usize,
-}
-
-#[derive(Debug, Clone)]
-struct NoSuchCellError {
-    point: SchematicPoint,
-}
-
-impl fmt::Display for NoSuchCellError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "Cell {{Row: {}, Col: {}}} not found.",
-            self.point.row, self.point.col
-        )
-    }
+pub struct SchematicPoint {
+    pub row: usize,
+    pub col: usize,
 }
 
 impl fmt::Display for SchematicPoint {
@@ -55,9 +47,11 @@ impl Index<SchematicPoint> for EngineSchematic {
 
 impl From<String> for EngineSchematic {
     fn from(value: String) -> Self {
-        let data: Vec<Vec<char>> = value.split('\n').map(|str| str.chars().collect()).collect();
+        let data: Vec<Vec<char>> = lines(&value)
+            .map(|(_, row)| row.chars().collect())
+            .collect();
         let height = data.len();
-        let width = data[0].len();
+        let width = data.first().map_or(0, Vec::len);
 
         EngineSchematic {
             height,
@@ -71,14 +65,8 @@ impl EngineSchematic {
     fn get_neighboring_points(&self, point: SchematicPoint) -> Vec<SchematicPoint> {
         let mut neighbors: Vec<SchematicPoint> = vec![];
 
-        let min_row: usize = match point.row.checked_sub(1) {
-            Some(min_row) => min_row,
-            None => 0,
-        };
-        let min_col: usize = match point.col.checked_sub(1) {
-            Some(min_col) => min_col,
-            None => 0,
-        };
+        let min_row: usize = point.row.saturating_sub(1);
+        let min_col: usize = point.col.saturating_sub(1);
         let max_row: usize = if point.row + 1 < self.height {
             point.row + 1
         } else {
@@ -106,12 +94,11 @@ impl EngineSchematic {
         let mut explored_set: HashSet<SchematicPoint> = HashSet::new();
         let mut numbers: Vec<i32> = vec![];
         for neighbor in self.get_neighboring_points(point) {
-            println!("{}", neighbor);
             if explored_set.contains(&neighbor) {
                 continue;
             }
             if NUMBERS.contains(&self[neighbor]) {
-                let mut pointer = neighbor.clone();
+                let mut pointer = neighbor;
                 loop {
                     if pointer.col == self.width || !NUMBERS.contains(&self[pointer]) {
                         pointer.col -= 1;
@@ -122,7 +109,7 @@ impl EngineSchematic {
                 let mut number = 0;
                 let mut pow = 0;
                 while let Some(digit) = self[pointer].to_digit(10) {
-                    explored_set.insert(pointer.clone());
+                    explored_set.insert(pointer);
                     number += digit as i32 * 10_i32.pow(pow);
                     pow += 1;
                     if pointer.col == 0 {
@@ -130,7 +117,6 @@ impl EngineSchematic {
                     }
                     pointer.col -= 1;
                 }
-                println!("Pushing: {}", number);
                 numbers.push(number);
             }
         }
@@ -138,7 +124,7 @@ impl EngineSchematic {
     }
 }
 
-fn part_one_solution(engine_schematic: EngineSchematic) -> i32 {
+fn part_one_solution(engine_schematic: &EngineSchematic) -> i32 {
     let mut answer_sum: u32 = 0;
 
     for row in 0..engine_schematic.height {
@@ -181,7 +167,7 @@ fn part_one_solution(engine_schematic: EngineSchematic) -> i32 {
     answer_sum as i32
 }
 
-fn part_two_solution(engine_schematic: EngineSchematic) -> i32 {
+fn part_two_solution(engine_schematic: &EngineSchematic) -> i32 {
     let mut answer: i32 = 0;
     for row in 0..engine_schematic.height {
         let mut pointer = SchematicPoint {
@@ -193,7 +179,7 @@ fn part_two_solution(engine_schematic: EngineSchematic) -> i32 {
                 let surrounding_numbers = engine_schematic.get_surrounding_numbers(pointer);
                 if surrounding_numbers.len() == 2 {
                     answer +=
-                        surrounding_numbers.get(0).unwrap() * surrounding_numbers.get(1).unwrap();
+                        surrounding_numbers.first().unwrap() * surrounding_numbers.get(1).unwrap();
                 }
             }
             if pointer.col == 0 {
@@ -205,11 +191,57 @@ fn part_two_solution(engine_schematic: EngineSchematic) -> i32 {
     answer
 }
 
-pub fn run(problem_data: String, solution_part: SolutionPart) -> Result<String, SolutionError> {
-    let engine_schematic = EngineSchematic::from(problem_data);
-    match solution_part {
-        SolutionPart::PartOne => Ok(part_one_solution(engine_schematic).to_string()),
-        SolutionPart::PartTwo => Ok(part_two_solution(engine_schematic).to_string()),
+pub struct DayThree;
+
+impl Solution for DayThree {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Gear Ratios";
+    const INPUT_FORMAT: &'static str = "A grid of equally wide rows of digits, symbols and `.`.";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("../../../inputs/2023/day03.example1.txt"),
+        part_one: Some("4361"),
+        part_two: Some("467835"),
+    }];
+
+    type Input = EngineSchematic;
+
+    fn parse(problem_data: &str) -> Result<Self::Input, SolutionError> {
+        // Every row has to be as wide as the first one, since the solvers walk
+        // the grid by index.
+        let mut rows = lines(problem_data);
+        let (_, first_row) = rows.next().unwrap_or((1, ""));
+        let width = first_row.chars().count();
+        if width == 0 {
+            return Err(
+                ParseError::new(DAY, 1, first_row, first_row, "a row of schematic cells").into(),
+            );
+        }
+        for (line_number, row) in rows {
+            if row.chars().count() != width {
+                // Point at the overflow of a long row, or the end of a short one.
+                let snippet = match row.char_indices().nth(width) {
+                    Some((offset, _)) => &row[offset..],
+                    None => &row[row.len()..],
+                };
+                return Err(ParseError::new(
+                    DAY,
+                    line_number,
+                    row,
+                    snippet,
+                    format!("a row of {} cells", width),
+                )
+                .into());
+            }
+        }
+        Ok(EngineSchematic::from(problem_data.to_string()))
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolutionError> {
+        Ok(part_one_solution(input).to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolutionError> {
+        Ok(part_two_solution(input).to_string())
     }
 }
 
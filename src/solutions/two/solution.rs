@@ -1,18 +1,23 @@
-use std::cmp::max;
+use std::{cmp::max, io::BufRead};
 
-use crate::{error::SolutionError, util::SolutionPart};
+use crate::{
+    error::{ParseError, SolutionError},
+    solutions::{Example, Solution},
+    util::text::{for_each_line, lines},
+};
 
+/// The cubes shown in one draw of a game.
 #[derive(Debug)]
-struct CubeSet {
-    red: i32,
-    green: i32,
-    blue: i32,
+pub struct CubeSet {
+    pub red: i32,
+    pub green: i32,
+    pub blue: i32,
 }
 
 #[derive(Debug)]
-struct Game {
-    index: i32,
-    draws: Vec<CubeSet>,
+pub struct Game {
+    pub index: i32,
+    pub draws: Vec<CubeSet>,
 }
 
 impl std::fmt::Display for Game {
@@ -26,33 +31,59 @@ impl std::fmt::Display for Game {
     }
 }
 
-fn extract_rolls(draw: &str, color: &str) -> Result<i32, SolutionError> {
-    match draw.replace(color, "").trim().parse::<i32>() {
-        Ok(drawn_color) => Ok(drawn_color),
-        Err(_) => Err(SolutionError::NoPossibleSolution),
+const DAY: u8 = 2;
+const DRAW_FORMAT: &str = "`<n> red|green|blue`";
+
+fn extract_rolls<'a>(
+    line_number: usize,
+    game_str: &str,
+    draw: &'a str,
+) -> Result<(i32, &'a str), ParseError> {
+    let draw = draw.trim();
+    let invalid_draw = || ParseError::new(DAY, line_number, game_str, draw, DRAW_FORMAT);
+
+    let (count, color) = draw.split_once(' ').ok_or_else(invalid_draw)?;
+    match count.parse::<i32>() {
+        Ok(drawn_color) => Ok((drawn_color, color)),
+        Err(_) => Err(invalid_draw()),
     }
 }
 
-fn extract_game_parts(game_str: &str) -> Result<Game, SolutionError> {
-    let split_game_str: Vec<&str> = game_str.split(':').collect();
-    let index = split_game_str[0]
-        .replace("Game ", "")
-        .parse::<i32>()
-        .unwrap();
+fn extract_game_parts(line_number: usize, game_str: &str) -> Result<Game, ParseError> {
+    let Some((game_label, draws_str)) = game_str.split_once(':') else {
+        return Err(ParseError::new(
+            DAY,
+            line_number,
+            game_str,
+            game_str,
+            "`Game <n>: <draws>`",
+        ));
+    };
+    let index = game_label
+        .strip_prefix("Game ")
+        .and_then(|index| index.parse::<i32>().ok())
+        .ok_or_else(|| ParseError::new(DAY, line_number, game_str, game_label, "`Game <n>`"))?;
     let mut draws: Vec<CubeSet> = vec![];
 
-    for draw_set in split_game_str[1].split(';') {
+    for draw_set in draws_str.split(';') {
         let mut red = 0;
         let mut green = 0;
         let mut blue = 0;
 
         for draw in draw_set.split(',') {
-            if draw.ends_with("red") {
-                red = extract_rolls(draw, "red")?;
-            } else if draw.ends_with("green") {
-                green = extract_rolls(draw, "green")?;
-            } else if draw.ends_with("blue") {
-                blue = extract_rolls(draw, "blue")?;
+            match extract_rolls(line_number, game_str, draw)? {
+                (count, "red") => red = count,
+                (count, "green") => green = count,
+                (count, "blue") => blue = count,
+                _ => {
+                    return Err(ParseError::new(
+                        DAY,
+                        line_number,
+                        game_str,
+                        draw.trim(),
+                        DRAW_FORMAT,
+                    ))
+                }
             }
         }
         draws.push(CubeSet { red, green, blue })
@@ -82,23 +113,72 @@ fn game_cubeset_power(game: &Game) -> i32 {
     min_cube_set.red * min_cube_set.green * min_cube_set.blue
 }
 
-pub fn run(problem_data: String, solution_part: SolutionPart) -> Result<String, SolutionError> {
-    let mut puzzle_answer: i32 = 0;
+pub struct DayTwo;
 
-    for game_str in problem_data.split('\n') {
-        let game = extract_game_parts(game_str)?;
+impl Solution for DayTwo {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Cube Conundrum";
+    const INPUT_FORMAT: &'static str =
+        "One game per line: `Game <n>: <n> red|green|blue, ...; ...`.";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("../../../inputs/2023/day02.example1.txt"),
+        part_one: Some("8"),
+        part_two: Some("2286"),
+    }];
 
-        match solution_part {
-            SolutionPart::PartOne => {
-                if game_is_valid(&game) {
-                    puzzle_answer += game.index;
-                }
-            }
-            SolutionPart::PartTwo => {
-                puzzle_answer += game_cubeset_power(&game);
-            }
+    type Input = Vec<Game>;
+
+    fn parse(problem_data: &str) -> Result<Self::Input, SolutionError> {
+        let mut games = vec![];
+        for (line_number, game_str) in lines(problem_data) {
+            games.push(extract_game_parts(line_number, game_str)?);
         }
+        Ok(games)
     }
 
-    Ok(puzzle_answer.to_string())
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, SolutionError> {
+        let mut games = vec![];
+        for_each_line(reader, |line_number, game_str| {
+            games.push(extract_game_parts(line_number, game_str)?);
+            Ok(())
+        })?;
+        Ok(games)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolutionError> {
+        let puzzle_answer: i32 = input
+            .iter()
+            .filter(|game| game_is_valid(game))
+            .map(|game| game.index)
+            .sum();
+        Ok(puzzle_answer.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolutionError> {
+        let puzzle_answer: i32 = input.iter().map(game_cubeset_power).sum();
+        Ok(puzzle_answer.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bad_draw_is_located() {
+        let err = extract_game_parts(3, "Game 3: 1 red; 8 gren, 2 blue").unwrap_err();
+
+        assert_eq!(err.day, 2);
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 16);
+        assert_eq!(err.snippet, "8 gren");
+    }
+
+    #[test]
+    fn test_bad_game_index_is_located() {
+        let err = extract_game_parts(1, "Gaem 1: 1 red").unwrap_err();
+
+        assert_eq!(err.column, 1);
+        assert_eq!(err.snippet, "Gaem 1");
+    }
 }
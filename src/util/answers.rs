@@ -0,0 +1,88 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::SolutionError, util::SolutionPart};
+
+/// The answers the puzzle site accepted for each day, kept next to the inputs
+/// they were computed from as `<inputs>/2023/answers.json`:
+///
+/// ```json
+/// { "1": { "part_one": "57346", "part_two": "57345" } }
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<u8, DayAnswers>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part_one: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part_two: Option<String>,
+}
+
+impl Answers {
+    /// Loads the answers at `path`, or none at all if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self, SolutionError> {
+        let answers = match fs::read_to_string(path) {
+            Ok(answers) => answers,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(source) => {
+                return Err(SolutionError::FileLoadError {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        serde_json::from_str(&answers).map_err(|err| SolutionError::InvalidFile {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SolutionError> {
+        let mut answers = serde_json::to_string_pretty(self).expect("answers serialize to JSON");
+        answers.push('\n');
+        fs::write(path, answers).map_err(|source| SolutionError::FileSaveError {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn get(&self, day: u8, part: SolutionPart) -> Option<&str> {
+        let day_answers = self.days.get(&day)?;
+        match part {
+            SolutionPart::PartOne => day_answers.part_one.as_deref(),
+            SolutionPart::PartTwo => day_answers.part_two.as_deref(),
+        }
+    }
+
+    pub fn record(&mut self, day: u8, part: SolutionPart, answer: String) {
+        let day_answers = self.days.entry(day).or_default();
+        match part {
+            SolutionPart::PartOne => day_answers.part_one = Some(answer),
+            SolutionPart::PartTwo => day_answers.part_two = Some(answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_round_trip_through_json() {
+        let mut answers = Answers::default();
+        answers.record(5, SolutionPart::PartTwo, String::from("46"));
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"{"5":{"part_two":"46"}}"#);
+
+        let answers: Answers = serde_json::from_str(&json).unwrap();
+        assert_eq!(answers.get(5, SolutionPart::PartTwo), Some("46"));
+        assert_eq!(answers.get(5, SolutionPart::PartOne), None);
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::error::SolutionError;
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// Lets whoever started a solve give up on it. Solvers can't be stopped from
+/// the outside, so long-running ones call `checkpoint` now and then and stop
+/// once the token they run under has been cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Runs `work` with this as the current thread's token.
+    pub fn scope<T>(&self, work: impl FnOnce() -> T) -> T {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let _restore = RestoreToken(previous);
+        work()
+    }
+
+    /// Cancels the token when the returned guard is dropped, such as when the
+    /// future holding it is given up on.
+    pub fn cancel_on_drop(&self) -> CancelOnDrop {
+        CancelOnDrop(self.clone())
+    }
+}
+
+pub struct CancelOnDrop(CancelToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// Puts back the token a `scope` replaced, even if its work panicked.
+struct RestoreToken(Option<CancelToken>);
+
+impl Drop for RestoreToken {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Fails with `SolutionError::Cancelled` once the token the current thread
+/// runs under has been cancelled. Without a token it never fails.
+pub fn checkpoint() -> Result<(), SolutionError> {
+    let cancelled = CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+    });
+    if cancelled {
+        Err(SolutionError::Cancelled)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_follows_scoped_token() {
+        let token = CancelToken::default();
+        assert!(checkpoint().is_ok());
+
+        token.scope(|| {
+            assert!(checkpoint().is_ok());
+            drop(token.cancel_on_drop());
+            assert!(matches!(checkpoint(), Err(SolutionError::Cancelled)));
+        });
+
+        assert!(checkpoint().is_ok());
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::PathBuf,
+};
+
+use serde::Deserialize;
+
+use crate::error::SolutionError;
+
+/// The puzzle year that the inputs directory is laid out for.
+pub const YEAR: u16 = 2023;
+
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+pub const INPUTS_ENV_VAR: &str = "AOC_INPUTS";
+/// Optional config file, read from the working directory.
+pub const CONFIG_FILE: &str = "aoc.json";
+
+/// Which of a day's input files to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFile {
+    Puzzle,
+    /// One of the worked examples from the puzzle text, numbered from 1.
+    Example(u8),
+}
+
+#[derive(Deserialize)]
+struct Config {
+    inputs: Option<PathBuf>,
+}
+
+/// The inputs directory, laid out as `<root>/2023/day05.txt` for a day's
+/// puzzle input and `<root>/2023/day05.example1.txt` for its examples.
+pub struct Inputs {
+    root: PathBuf,
+}
+
+impl Inputs {
+    pub fn new(root: PathBuf) -> Self {
+        Inputs { root }
+    }
+
+    /// Picks the inputs root from, in order, `flag` (which clap also fills
+    /// from `AOC_INPUTS`), the `inputs` key of `aoc.json`, or `inputs`.
+    pub fn resolve(flag: Option<PathBuf>) -> Result<Self, SolutionError> {
+        if let Some(root) = flag {
+            return Ok(Inputs::new(root));
+        }
+
+        let config_path = PathBuf::from(CONFIG_FILE);
+        let config_root = match fs::read_to_string(&config_path) {
+            Ok(config) => match serde_json::from_str::<Config>(&config) {
+                Ok(config) => config.inputs,
+                Err(err) => {
+                    return Err(SolutionError::InvalidFile {
+                        path: config_path,
+                        message: err.to_string(),
+                    })
+                }
+            },
+            Err(_) => None,
+        };
+
+        Ok(Inputs::new(
+            config_root.unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR)),
+        ))
+    }
+
+    pub fn path(&self, day: u8, file: InputFile) -> PathBuf {
+        let file_name = match file {
+            InputFile::Puzzle => format!("day{:02}.txt", day),
+            InputFile::Example(example) => format!("day{:02}.example{}.txt", day, example),
+        };
+        self.root.join(YEAR.to_string()).join(file_name)
+    }
+
+    /// Where the accepted answers for these inputs are recorded.
+    pub fn answers_path(&self) -> PathBuf {
+        self.root.join(YEAR.to_string()).join("answers.json")
+    }
+
+    pub fn load(&self, day: u8, file: InputFile) -> Result<String, SolutionError> {
+        let path = self.path(day, file);
+        if !path.exists() {
+            return Err(SolutionError::MissingInput { day, path });
+        }
+        super::load_file(path)
+    }
+
+    /// Like `load`, but for reading the file as a stream.
+    pub fn open(&self, day: u8, file: InputFile) -> Result<BufReader<File>, SolutionError> {
+        let path = self.path(day, file);
+        if !path.exists() {
+            return Err(SolutionError::MissingInput { day, path });
+        }
+        super::open_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_input_file_names() {
+        let inputs = Inputs::new(PathBuf::from("inputs"));
+
+        assert_eq!(
+            inputs.path(5, InputFile::Puzzle),
+            Path::new("inputs/2023/day05.txt")
+        );
+        assert_eq!(
+            inputs.path(12, InputFile::Example(2)),
+            Path::new("inputs/2023/day12.example2.txt")
+        );
+    }
+}
@@ -1,15 +1,78 @@
-use std::path::PathBuf;
+pub mod answers;
+pub mod cancel;
+pub mod inputs;
+pub mod progress;
+pub mod text;
 
-use crate::error::SolutionError;
+use std::{fs::File, io::BufReader, path::PathBuf, str::FromStr};
+
+use clap::ValueEnum;
+
+use crate::error::{ParseError, SolutionError};
 
 pub fn load_file(path: PathBuf) -> Result<String, SolutionError> {
-    if let Ok(data) = std::fs::read_to_string(path) {
-        Ok(data)
-    } else {
-        Err(SolutionError::FileLoadError)
+    match std::fs::read_to_string(&path) {
+        Ok(data) => Ok(data),
+        Err(source) => Err(SolutionError::FileLoadError { path, source }),
+    }
+}
+
+pub fn open_file(path: PathBuf) -> Result<BufReader<File>, SolutionError> {
+    match File::open(&path) {
+        Ok(file) => Ok(BufReader::new(file)),
+        Err(source) => Err(SolutionError::FileLoadError { path, source }),
     }
 }
+
+/// Parses every whitespace separated number in `segment`, which has to be a
+/// slice of `line`, pointing any error at the offending token.
+pub fn parse_numbers<T: FromStr>(
+    day: u8,
+    line_number: usize,
+    line: &str,
+    segment: &str,
+) -> Result<Vec<T>, ParseError> {
+    segment
+        .split_whitespace()
+        .map(|token| {
+            token
+                .parse::<T>()
+                .map_err(|_| ParseError::new(day, line_number, line, token, "a number"))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SolutionPart {
     PartOne,
     PartTwo,
 }
+
+impl SolutionPart {
+    pub fn number(self) -> u8 {
+        match self {
+            SolutionPart::PartOne => 1,
+            SolutionPart::PartTwo => 2,
+        }
+    }
+}
+
+/// Which parts of a day to solve in one go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PartSelection {
+    #[value(name = "1", alias = "one")]
+    One,
+    #[value(name = "2", alias = "two")]
+    Two,
+    Both,
+}
+
+impl PartSelection {
+    pub fn parts(self) -> &'static [SolutionPart] {
+        match self {
+            PartSelection::One => &[SolutionPart::PartOne],
+            PartSelection::Two => &[SolutionPart::PartTwo],
+            PartSelection::Both => &[SolutionPart::PartOne, SolutionPart::PartTwo],
+        }
+    }
+}
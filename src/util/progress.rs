@@ -0,0 +1,128 @@
+use std::{
+    cell::{Cell, RefCell},
+    sync::Arc,
+};
+
+use serde::Serialize;
+
+use crate::util::SolutionPart;
+
+/// Where progress reports go, such as a server's event stream or a terminal
+/// progress bar.
+pub type ProgressSink = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+thread_local! {
+    static CURRENT: RefCell<Option<ProgressSink>> = const { RefCell::new(None) };
+    static STEP: Cell<Step> = const { Cell::new(Step::Parse) };
+}
+
+/// What a solver is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "phase", rename_all = "snake_case")]
+pub enum Step {
+    Parse,
+    Part { part: u8 },
+}
+
+/// How far into its current step a solver has got. `total` is `None` until
+/// the solver says how much work there is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Progress {
+    #[serde(flatten)]
+    pub step: Step,
+    pub done: u64,
+    pub total: Option<u64>,
+}
+
+/// Runs `work` with progress reported on this thread going to `sink`.
+pub fn scope<T>(sink: ProgressSink, work: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(sink)));
+    let _restore = RestoreSink(previous);
+    work()
+}
+
+/// Puts back the sink a `scope` replaced, even if its work panicked.
+struct RestoreSink(Option<ProgressSink>);
+
+impl Drop for RestoreSink {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+fn send(progress: Progress) {
+    CURRENT.with(|current| {
+        if let Some(sink) = current.borrow().as_ref() {
+            sink(&progress);
+        }
+    });
+}
+
+/// Marks the start of parsing, or of solving `part`, for the reports that
+/// follow.
+pub(crate) fn start(part: Option<SolutionPart>) {
+    let step = match part {
+        None => Step::Parse,
+        Some(part) => Step::Part {
+            part: part.number(),
+        },
+    };
+    STEP.with(|current| current.set(step));
+    send(Progress {
+        step,
+        done: 0,
+        total: None,
+    });
+}
+
+/// Reports that `done` of the `total` items in the current step are done.
+/// Reports go nowhere unless the solver runs in a `scope`, so solvers can
+/// call this freely, though not for every item of a hot loop.
+pub fn report(done: u64, total: u64) {
+    send(Progress {
+        step: STEP.with(Cell::get),
+        done,
+        total: Some(total),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn test_reports_reach_scoped_sink() {
+        let reports = Arc::new(Mutex::new(vec![]));
+        let sink_reports = Arc::clone(&reports);
+        let sink: ProgressSink = Arc::new(move |progress| {
+            sink_reports.lock().unwrap().push(*progress);
+        });
+
+        report(1, 2);
+        scope(sink, || {
+            start(Some(SolutionPart::PartTwo));
+            report(5, 10);
+        });
+        report(3, 4);
+
+        let part_two = Step::Part { part: 2 };
+        assert_eq!(
+            *reports.lock().unwrap(),
+            [
+                Progress {
+                    step: part_two,
+                    done: 0,
+                    total: None
+                },
+                Progress {
+                    step: part_two,
+                    done: 5,
+                    total: Some(10)
+                },
+            ]
+        );
+    }
+}
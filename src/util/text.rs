@@ -0,0 +1,213 @@
+//! The one place puzzle input is split up, so that every day reads it the
+//! same way however it was saved: with or without a byte order mark, with
+//! `\n` or `\r\n` line endings, and with any number of trailing blank lines.
+
+use std::io::{self, BufRead, Read};
+
+use crate::error::SolutionError;
+
+const BOM: char = '\u{feff}';
+
+/// `problem_data` without a leading byte order mark or trailing blank lines.
+/// Line endings are left for `lines` to strip, so this never copies.
+pub fn normalize(problem_data: &str) -> &str {
+    let problem_data = problem_data.strip_prefix(BOM).unwrap_or(problem_data);
+    let content_end = problem_data.trim_end().len();
+    // Keep the rest of the last line with content, up to its line ending.
+    match problem_data[content_end..].find('\n') {
+        Some(line_end) => &problem_data[..content_end + line_end],
+        None => problem_data,
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// The lines of the normalized `problem_data`, numbered from 1 and without
+/// their line endings. Empty input has no lines at all.
+pub fn lines(problem_data: &str) -> impl Iterator<Item = (usize, &str)> {
+    let problem_data = normalize(problem_data);
+    problem_data
+        .split('\n')
+        .take_while(move |_| !problem_data.is_empty())
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+}
+
+/// The runs of lines between blank lines, each line numbered as in `lines`.
+pub fn paragraphs(problem_data: &str) -> impl Iterator<Item = Vec<(usize, &str)>> {
+    let mut lines = lines(problem_data).peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|(_, line)| is_blank(line)).is_some() {}
+        let paragraph: Vec<_> =
+            std::iter::from_fn(|| lines.next_if(|(_, line)| !is_blank(line))).collect();
+        (!paragraph.is_empty()).then_some(paragraph)
+    })
+}
+
+/// `lines` for an input that is read from `reader` one line at a time, so
+/// that the whole input is never held in memory at once. `each` is called
+/// with every line's number and text.
+pub fn for_each_line(
+    reader: &mut dyn BufRead,
+    mut each: impl FnMut(usize, &str) -> Result<(), SolutionError>,
+) -> Result<(), SolutionError> {
+    let mut line = String::new();
+    let mut line_number = 0;
+    // Blank lines are held back until a line with content shows they aren't
+    // trailing.
+    let mut blank_lines: Vec<String> = vec![];
+    loop {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(SolutionError::InputReadError)?;
+        if read == 0 {
+            return Ok(());
+        }
+        line_number += 1;
+
+        let mut text = line.strip_suffix('\n').unwrap_or(&line);
+        text = text.strip_suffix('\r').unwrap_or(text);
+        if line_number == 1 {
+            text = text.strip_prefix(BOM).unwrap_or(text);
+        }
+        if is_blank(text) {
+            blank_lines.push(text.to_string());
+            continue;
+        }
+        let first_blank_line = line_number - blank_lines.len();
+        for (offset, blank_line) in blank_lines.drain(..).enumerate() {
+            each(first_blank_line + offset, &blank_line)?;
+        }
+        each(line_number, text)?;
+    }
+}
+
+/// Wraps `reader` and keeps the last line read through it, so that a parse
+/// error in a streamed input can still show the line it points at without
+/// the whole input being kept.
+pub struct LastLine<R> {
+    reader: R,
+    line_number: usize,
+    line: Vec<u8>,
+    line_ended: bool,
+}
+
+impl<R: BufRead> LastLine<R> {
+    pub fn new(reader: R) -> Self {
+        LastLine {
+            reader,
+            line_number: 0,
+            line: vec![],
+            line_ended: true,
+        }
+    }
+
+    /// The number and text of the last line read, as `for_each_line` gives
+    /// it, or `None` before anything is read.
+    pub fn get(&self) -> Option<(usize, String)> {
+        if self.line_number == 0 {
+            return None;
+        }
+        let line = String::from_utf8_lossy(&self.line);
+        let mut text = line.strip_suffix('\r').unwrap_or(&line);
+        if self.line_number == 1 {
+            text = text.strip_prefix(BOM).unwrap_or(text);
+        }
+        Some((self.line_number, text.to_string()))
+    }
+}
+
+impl<R: BufRead> Read for LastLine<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = {
+            let available = self.fill_buf()?;
+            let read = available.len().min(buf.len());
+            buf[..read].copy_from_slice(&available[..read]);
+            read
+        };
+        self.consume(read);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for LastLine<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // Whatever is consumed is still in the reader's buffer.
+        if let Ok(consumed) = self.reader.fill_buf() {
+            for &byte in &consumed[..amt.min(consumed.len())] {
+                if self.line_ended {
+                    self.line.clear();
+                    self.line_number += 1;
+                    self.line_ended = false;
+                }
+                if byte == b'\n' {
+                    self.line_ended = true;
+                } else {
+                    self.line.push(byte);
+                }
+            }
+        }
+        self.reader.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_are_normalized() {
+        let problem_data = "\u{feff}Game 1\r\n \r\nGame 2 \r\n\r\n  \n";
+        let expected = [(1, "Game 1"), (2, " "), (3, "Game 2 ")];
+
+        assert_eq!(lines(problem_data).collect::<Vec<_>>(), expected);
+
+        let mut streamed = vec![];
+        for_each_line(&mut problem_data.as_bytes(), |line_number, line| {
+            streamed.push((line_number, line.to_string()));
+            Ok(())
+        })
+        .unwrap();
+        let streamed: Vec<_> = streamed
+            .iter()
+            .map(|(line_number, line)| (*line_number, line.as_str()))
+            .collect();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_paragraphs_split_on_blank_lines() {
+        let paragraphs: Vec<_> = paragraphs("seeds: 1\n\na:\n1 2 3\n \nb:\n").collect();
+
+        assert_eq!(
+            paragraphs,
+            [
+                vec![(1, "seeds: 1")],
+                vec![(3, "a:"), (4, "1 2 3")],
+                vec![(6, "b:")]
+            ]
+        );
+        assert_eq!(lines("").count(), 0);
+    }
+
+    #[test]
+    fn test_last_line_is_kept() {
+        let mut reader = LastLine::new("\u{feff}Game 1\r\nGame 2\r\n".as_bytes());
+        assert_eq!(reader.get(), None);
+
+        let mut first = String::new();
+        reader.read_line(&mut first).unwrap();
+        assert_eq!(reader.get(), Some((1, String::from("Game 1"))));
+
+        io::read_to_string(&mut reader).unwrap();
+        assert_eq!(reader.get(), Some((2, String::from("Game 2"))));
+    }
+}
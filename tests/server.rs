@@ -0,0 +1,683 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use advent_of_code::{
+    server::{app, app_with, ServerOptions},
+    util::cancel::checkpoint,
+    Solution, SolutionError, Solver,
+};
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::CONTENT_TYPE, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+/// Declares `$day`, a day 1 solution for tests that solves with the given
+/// bodies, and `$solvers`, a registry holding only it.
+macro_rules! test_day {
+    (
+        $day:ident, $solvers:ident, $input:ty,
+        parse: |$data:ident| $parse:expr,
+        part_one: |$one:ident| $part_one:expr,
+        part_two: |$two:ident| $part_two:expr $(,)?
+    ) => {
+        struct $day;
+
+        impl Solution for $day {
+            const DAY: u8 = 1;
+            const TITLE: &'static str = stringify!($day);
+            const INPUT_FORMAT: &'static str = "Anything.";
+
+            type Input = $input;
+
+            fn parse($data: &str) -> Result<Self::Input, SolutionError> {
+                $parse
+            }
+
+            fn part_one($one: &Self::Input) -> Result<String, SolutionError> {
+                $part_one
+            }
+
+            fn part_two($two: &Self::Input) -> Result<String, SolutionError> {
+                $part_two
+            }
+        }
+
+        static $solvers: &[&dyn Solver] = &[&$day];
+    };
+}
+
+const DAY_TWO: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green\nGame 2: 1 red, 2 green";
+
+async fn post(uri: &str, content_type: &str, body: impl Into<Body>) -> (StatusCode, Value) {
+    let request = Request::post(uri)
+        .header(CONTENT_TYPE, content_type)
+        .body(body.into())
+        .unwrap();
+    send(request).await
+}
+
+async fn get(uri: &str) -> (StatusCode, Value) {
+    send(Request::get(uri).body(Body::empty()).unwrap()).await
+}
+
+async fn send(request: Request<Body>) -> (StatusCode, Value) {
+    send_to(app(), request).await
+}
+
+async fn send_to(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_solve_json_with_escaped_newlines() {
+    let body = json!({"problem": 2, "part": 1, "data": DAY_TWO}).to_string();
+
+    let (status, output) = post("/solve", "application/json", body).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(output["answer"], "3");
+}
+
+#[tokio::test]
+async fn test_solve_json_backticks_are_opt_in() {
+    let data = DAY_TWO.replace('\n', "`");
+
+    let body = json!({"problem": 2, "part": 1, "data": data}).to_string();
+    let (status, _) = post("/solve", "application/json", body).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let body = json!({"problem": 2, "part": 1, "data": data, "backtick_newlines": true});
+    let (status, output) = post("/solve", "application/json", body.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(output["answer"], "3");
+}
+
+#[tokio::test]
+async fn test_solve_text_body_with_query() {
+    let (status, output) = post("/solve?problem=2&part=both", "text/plain", DAY_TWO).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(output["parts"][0]["answer"], "3");
+    assert_eq!(output["parts"][1]["answer"], "24");
+
+    let (status, _) = post("/solve?part=1", "text/plain", DAY_TWO).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_solve_multipart_upload() {
+    let body = format!(
+        "--boundary\r\n\
+         Content-Disposition: form-data; name=\"problem\"\r\n\r\n\
+         2\r\n\
+         --boundary\r\n\
+         Content-Disposition: form-data; name=\"data\"; filename=\"day02.txt\"\r\n\
+         Content-Type: text/plain\r\n\r\n\
+         {}\r\n\
+         --boundary--\r\n",
+        DAY_TWO
+    );
+
+    let (status, output) = post(
+        "/solve?part=2",
+        "multipart/form-data; boundary=boundary",
+        body,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(output["answer"], "24");
+}
+
+#[tokio::test]
+async fn test_solve_rejects_other_content_types() {
+    let (status, output) = post("/solve?problem=2&part=1", "text/csv", DAY_TWO).await;
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(output["code"], "unsupported_media_type");
+    assert!(output["message"].as_str().unwrap().contains("text/csv"));
+}
+
+#[tokio::test]
+async fn test_list_problems() {
+    let (status, problems) = get("/problems").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(problems.as_array().unwrap().len(), 6);
+    assert_eq!(problems[1]["day"], 2);
+    assert_eq!(problems[1]["title"], "Cube Conundrum");
+    assert_eq!(problems[1]["parts"], json!([1, 2]));
+    assert_eq!(problems[1]["has_examples"], true);
+}
+
+#[tokio::test]
+async fn test_get_problem_with_examples() {
+    let (status, problem) = get("/problems/1").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(problem["title"], "Trebuchet?!");
+    assert_eq!(problem["examples"][0]["part_one"], "142");
+    assert_eq!(problem["examples"][1]["part_one"], Value::Null);
+    assert_eq!(problem["examples"][1]["part_two"], "281");
+
+    let (status, _) = get("/problems/25").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+async fn solve_json(body: Value) -> (StatusCode, Value) {
+    post("/solve", "application/json", body.to_string()).await
+}
+
+#[tokio::test]
+async fn test_solve_unknown_day_is_not_found() {
+    let (status, error) = solve_json(json!({"problem": 25, "part": 1, "data": ""})).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(error["code"], "unknown_problem");
+    assert_eq!(error["message"], "day 25 has no solution");
+    assert_eq!(error["day"], 25);
+    assert_eq!(error["part"], 1);
+}
+
+#[tokio::test]
+async fn test_solve_unparseable_input_is_unprocessable() {
+    let data = "Game 1: 3 blue\nGame 2: 4 gren";
+    let (status, error) = solve_json(json!({"problem": 2, "part": "both", "data": data})).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["code"], "invalid_input");
+    assert_eq!(error["day"], 2);
+    assert_eq!(error["part"], Value::Null);
+    assert_eq!(error["location"]["line"], 2);
+    assert_eq!(error["location"]["column"], 9);
+    assert_eq!(error["location"]["snippet"], "4 gren");
+}
+
+#[tokio::test]
+async fn test_solve_bad_part_is_bad_request() {
+    let (status, error) = solve_json(json!({"problem": 2, "part": 3, "data": DAY_TWO})).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["code"], "invalid_part");
+    assert_eq!(error["day"], 2);
+    assert!(error.get("location").is_none());
+}
+
+test_day!(
+    Explodes, EXPLODES, (),
+    parse: |_problem_data| Ok(()),
+    part_one: |_input| panic!("boom"),
+    part_two: |_input| Err(SolutionError::NoPossibleSolution),
+);
+
+#[tokio::test]
+async fn test_solve_panic_is_internal_error() {
+    let request = |part: &str| {
+        Request::post(format!("/solve?problem=1&part={}", part))
+            .header(CONTENT_TYPE, "text/plain")
+            .body(Body::from("input"))
+            .unwrap()
+    };
+
+    let (status, error) = send_to(app_with(EXPLODES, ServerOptions::default()), request("1")).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(error["code"], "panicked");
+    assert_eq!(error["message"], "solver panicked: boom");
+    assert_eq!(error["part"], 1);
+
+    let (status, output) = send_to(
+        app_with(EXPLODES, ServerOptions::default()),
+        request("both"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(output["parts"][0]["error"]["code"], "panicked");
+    assert_eq!(output["parts"][1]["error"]["code"], "no_solution");
+}
+
+#[tokio::test]
+async fn test_solve_panic_reports_request_id_and_server_keeps_going() {
+    let app = app_with(EXPLODES, ServerOptions::default());
+    let request = Request::post("/solve?problem=1&part=1")
+        .header(CONTENT_TYPE, "text/plain")
+        .header("x-request-id", "dashboard-42")
+        .body(Body::from("input"))
+        .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.headers()["x-request-id"], "dashboard-42");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let error: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error["message"], "solver panicked: boom");
+    assert_eq!(error["request_id"], "dashboard-42");
+
+    let request = Request::get("/problems/1").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers()["x-request-id"].is_empty());
+}
+
+/// Waits for its solve to be cancelled, then sets `stopped` if given.
+fn spin(stopped: Option<&AtomicBool>) -> Result<String, SolutionError> {
+    loop {
+        if let Err(err) = checkpoint() {
+            if let Some(stopped) = stopped {
+                stopped.store(true, Ordering::SeqCst);
+            }
+            return Err(err);
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+test_day!(
+    Spins, SPINS, (),
+    parse: |_problem_data| Ok(()),
+    part_one: |_input| spin(None),
+    part_two: |_input| spin(None),
+);
+
+/// Set once `TimesOut` notices it was cancelled. Only the timeout test runs
+/// `TimesOut`, so no other test's cancellation can set it.
+static TIMES_OUT_STOPPED: AtomicBool = AtomicBool::new(false);
+
+test_day!(
+    TimesOut, TIMES_OUT, (),
+    parse: |_problem_data| Ok(()),
+    part_one: |_input| spin(Some(&TIMES_OUT_STOPPED)),
+    part_two: |_input| spin(Some(&TIMES_OUT_STOPPED)),
+);
+
+#[tokio::test]
+async fn test_solve_timeout_cancels_solver() {
+    let options = ServerOptions {
+        timeout: Duration::from_millis(50),
+        ..ServerOptions::default()
+    };
+    let request = Request::post("/solve?problem=1&part=1")
+        .header(CONTENT_TYPE, "text/plain")
+        .body(Body::from("input"))
+        .unwrap();
+
+    let (status, error) = send_to(app_with(TIMES_OUT, options), request).await;
+    assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+    assert_eq!(error["code"], "timeout");
+    assert_eq!(error["day"], 1);
+
+    for _ in 0..100 {
+        if TIMES_OUT_STOPPED.load(Ordering::SeqCst) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("the solver kept running after the request timed out");
+}
+
+/// How many times `Counts` has parsed an input.
+static COUNTS_PARSED: AtomicUsize = AtomicUsize::new(0);
+
+test_day!(
+    Counts, COUNTS, usize,
+    parse: |problem_data| {
+        COUNTS_PARSED.fetch_add(1, Ordering::SeqCst);
+        Ok(problem_data.lines().count())
+    },
+    part_one: |input| Ok(input.to_string()),
+    part_two: |input| Ok((input * 2).to_string()),
+);
+
+#[tokio::test]
+async fn test_solve_answers_are_cached_by_input() {
+    let options = ServerOptions {
+        admin: true,
+        ..ServerOptions::default()
+    };
+    let app = app_with(COUNTS, options);
+    let solve = |data: &'static str| {
+        let app = app.clone();
+        async move {
+            let request = Request::post("/solve?problem=1&part=both")
+                .header(CONTENT_TYPE, "text/plain")
+                .body(Body::from(data))
+                .unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            let cache = response.headers()["x-cache"].to_str().unwrap().to_string();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let output: Value = serde_json::from_slice(&body).unwrap();
+            (cache, output["parts"][1]["answer"].clone())
+        }
+    };
+
+    assert_eq!(solve("a\nb").await, ("MISS".to_string(), json!("4")));
+    assert_eq!(
+        solve("a\r\nb\r\n\r\n").await,
+        ("HIT".to_string(), json!("4"))
+    );
+    assert_eq!(COUNTS_PARSED.load(Ordering::SeqCst), 1);
+
+    let request = Request::delete("/admin/cache").body(Body::empty()).unwrap();
+    let (status, cleared) = send_to(app.clone(), request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cleared["cleared"], 2);
+
+    assert_eq!(solve("a\nb").await, ("MISS".to_string(), json!("4")));
+    assert_eq!(COUNTS_PARSED.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_admin_routes_are_opt_in() {
+    let request = Request::delete("/admin/cache").body(Body::empty()).unwrap();
+    let response = app().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+/// Polls job `id` until its status is not one of `waiting`.
+async fn job_after(app: &Router, id: &str, waiting: &[&str]) -> Value {
+    for _ in 0..100 {
+        let request = Request::get(format!("/jobs/{}", id))
+            .body(Body::empty())
+            .unwrap();
+        let (status, job) = send_to(app.clone(), request).await;
+        assert_eq!(status, StatusCode::OK);
+        if !waiting.iter().any(|status| job["status"] == *status) {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("job {} stayed {:?}", id, waiting);
+}
+
+#[tokio::test]
+async fn test_job_reports_answer_and_timings() {
+    let app = app();
+    let body = json!({"problem": 2, "part": "both", "data": DAY_TWO}).to_string();
+    let request = Request::post("/jobs")
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let job: Value = serde_json::from_slice(&body).unwrap();
+    let id = job["id"].as_str().unwrap();
+    assert_eq!(location, format!("/jobs/{}", id));
+    assert_eq!(job["parts"], json!([1, 2]));
+
+    let job = job_after(&app, id, &["queued", "running"]).await;
+    assert_eq!(job["status"], "done");
+    assert_eq!(job["parts"][0]["answer"], "3");
+    assert_eq!(job["parts"][1]["answer"], "24");
+    assert_eq!(job["timings"]["solve"]["parts"][1]["part"], 2);
+    assert!(job["timings"]["running_ns"].is_u64());
+}
+
+#[tokio::test]
+async fn test_job_can_be_cancelled_running_or_queued() {
+    let options = ServerOptions {
+        job_workers: 1,
+        ..ServerOptions::default()
+    };
+    let app = app_with(SPINS, options);
+    let create = || {
+        let app = app.clone();
+        async move {
+            let request = Request::post("/jobs?problem=1&part=1")
+                .header(CONTENT_TYPE, "text/plain")
+                .body(Body::from("input"))
+                .unwrap();
+            let (status, job) = send_to(app, request).await;
+            assert_eq!(status, StatusCode::ACCEPTED);
+            job["id"].as_str().unwrap().to_string()
+        }
+    };
+    let cancel = |id: String| {
+        let app = app.clone();
+        async move {
+            let request = Request::delete(format!("/jobs/{}", id))
+                .body(Body::empty())
+                .unwrap();
+            send_to(app, request).await
+        }
+    };
+
+    let running = create().await;
+    let queued = create().await;
+    let (_, job) = cancel(queued.clone()).await;
+    assert_eq!(job["status"], "cancelled");
+    let (_, job) = cancel(running.clone()).await;
+    assert_eq!(job["status"], "cancelled");
+
+    // With the only worker free again, new jobs still run.
+    let next = create().await;
+    let job = job_after(&app, &next, &["queued"]).await;
+    assert_eq!(job["status"], "running");
+    cancel(next).await;
+
+    let request = Request::get("/jobs/nope").body(Body::empty()).unwrap();
+    let (status, error) = send_to(app, request).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(error["code"], "unknown_job");
+}
+
+#[tokio::test]
+async fn test_job_queue_is_bounded() {
+    let options = ServerOptions {
+        job_workers: 1,
+        max_queued_jobs: 1,
+        ..ServerOptions::default()
+    };
+    let app = app_with(SPINS, options);
+    let create = || {
+        let request = Request::post("/jobs?problem=1&part=1")
+            .header(CONTENT_TYPE, "text/plain")
+            .body(Body::from("input"))
+            .unwrap();
+        send_to(app.clone(), request)
+    };
+
+    let (_, running) = create().await;
+    let running = running["id"].as_str().unwrap();
+    job_after(&app, running, &["queued"]).await;
+    let (status, queued) = create().await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let (status, error) = create().await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(error["code"], "queue_full");
+
+    for id in [running, queued["id"].as_str().unwrap()] {
+        let request = Request::delete(format!("/jobs/{}", id))
+            .body(Body::empty())
+            .unwrap();
+        send_to(app.clone(), request).await;
+    }
+}
+
+#[tokio::test]
+async fn test_jobs_queue_only_behind_busy_workers() {
+    let options = ServerOptions {
+        job_workers: 2,
+        max_queued_jobs: 0,
+        ..ServerOptions::default()
+    };
+    let app = app_with(EXPLODES, options);
+    for _ in 0..2 {
+        let request = Request::post("/jobs?problem=1&part=1")
+            .header(CONTENT_TYPE, "text/plain")
+            .body(Body::from("input"))
+            .unwrap();
+        let (status, _) = send_to(app.clone(), request).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+    }
+}
+
+#[tokio::test]
+async fn test_job_error_has_request_id() {
+    let app = app_with(EXPLODES, ServerOptions::default());
+    let request = Request::post("/jobs?problem=1&part=1")
+        .header(CONTENT_TYPE, "text/plain")
+        .header("x-request-id", "job-7")
+        .body(Body::from("input"))
+        .unwrap();
+
+    let (status, job) = send_to(app.clone(), request).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job = job_after(&app, job["id"].as_str().unwrap(), &["queued", "running"]).await;
+    assert_eq!(job["status"], "failed");
+    assert_eq!(job["error"]["code"], "panicked");
+    assert_eq!(job["error"]["request_id"], "job-7");
+}
+
+/// The name and data of each server-sent event `app` answers `request` with.
+async fn stream_events(app: Router, request: Request<Body>) -> Vec<(String, Value)> {
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/event-stream");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec())
+        .unwrap()
+        .split("\n\n")
+        .filter_map(|event| {
+            let name = event
+                .lines()
+                .find_map(|line| line.strip_prefix("event: "))?;
+            let data = event.lines().find_map(|line| line.strip_prefix("data: "))?;
+            Some((name.to_string(), serde_json::from_str(data).unwrap()))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_solve_stream_sends_progress_then_answer() {
+    let data = include_str!("../inputs/2023/day05.example1.txt");
+    let request = Request::post("/solve/stream?problem=5&part=2")
+        .header(CONTENT_TYPE, "text/plain")
+        .body(Body::from(data))
+        .unwrap();
+
+    let events = stream_events(app(), request).await;
+    let (last, progress) = events.split_last().unwrap();
+    assert_eq!(last, &("answer".to_string(), json!({"answer": "46"})));
+    assert!(progress.iter().all(|(name, _)| name == "progress"));
+    assert!(progress
+        .iter()
+        .any(|(_, report)| report["phase"] == "part" && report["part"] == 2));
+}
+
+#[tokio::test]
+async fn test_solve_stream_error_has_request_id() {
+    let request = Request::post("/solve/stream?problem=1&part=1")
+        .header(CONTENT_TYPE, "text/plain")
+        .header("x-request-id", "stream-3")
+        .body(Body::from("input"))
+        .unwrap();
+
+    let events = stream_events(app_with(EXPLODES, ServerOptions::default()), request).await;
+    let (name, error) = events.last().unwrap();
+    assert_eq!(name, "error");
+    assert_eq!(error["code"], "panicked");
+    assert_eq!(error["request_id"], "stream-3");
+}
+
+#[tokio::test]
+async fn test_solve_batch_reports_each_item_in_order() {
+    let body = json!([
+        {"problem": 2, "part": 1, "data": DAY_TWO},
+        {"problem": 99, "part": 1, "data": "input"},
+        {"problem": 2, "data": DAY_TWO},
+        {"problem": 2, "part": "both", "data": DAY_TWO},
+    ]);
+
+    let (status, outputs) = post("/solve/batch", "application/json", body.to_string()).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(outputs[0], json!({"status": 200, "answer": "3"}));
+    assert_eq!(outputs[1]["status"], 404);
+    assert_eq!(outputs[1]["error"]["code"], "unknown_problem");
+    assert_eq!(outputs[2]["status"], 400);
+    assert_eq!(outputs[2]["error"]["code"], "bad_request");
+    assert_eq!(outputs[3]["parts"][1]["answer"], "24");
+
+    let (status, error) = post("/solve/batch", "application/json", "{}").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["code"], "bad_request");
+}
+
+/// The lines `GET /metrics` answers with.
+async fn metrics(app: Router) -> Vec<String> {
+    let request = Request::get("/metrics").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec())
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_metrics_count_requests_solves_and_cache() {
+    let app = app();
+    for _ in 0..2 {
+        let body = json!({"problem": 2, "part": 1, "data": DAY_TWO}).to_string();
+        let request = Request::post("/solve")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap();
+    }
+    let request = Request::get("/nowhere").body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap();
+
+    let metrics = metrics(app).await;
+    for line in [
+        "aoc_http_requests_total{method=\"POST\",route=\"/solve\",status=\"200\"} 2",
+        "aoc_http_requests_total{method=\"GET\",route=\"unmatched\",status=\"404\"} 1",
+        "aoc_solve_duration_seconds_count{day=\"2\",part=\"1\"} 1",
+        "aoc_parse_duration_seconds_count{day=\"2\"} 1",
+        "aoc_input_bytes_count 2",
+        "aoc_cache_hits_total 1",
+        "aoc_cache_misses_total 1",
+        "aoc_cache_hit_ratio 0.5",
+        "aoc_jobs_in_flight{status=\"running\"} 0",
+        "aoc_solver_panics_total 0",
+    ] {
+        assert!(
+            metrics.iter().any(|metric| metric == line),
+            "missing {}",
+            line
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_metrics_count_solver_panics() {
+    let app = app_with(EXPLODES, ServerOptions::default());
+    for part in ["1", "both"] {
+        let request = Request::post(format!("/solve?problem=1&part={}", part))
+            .header(CONTENT_TYPE, "text/plain")
+            .body(Body::from("input"))
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap();
+    }
+
+    let metrics = metrics(app).await;
+    assert!(metrics
+        .iter()
+        .any(|metric| metric == "aoc_solver_panics_total 2"));
+}
@@ -0,0 +1,83 @@
+use advent_of_code::{
+    find,
+    solutions::{five::DayFive, four::DayFour, six::DaySix, two::DayTwo},
+    util::cancel::CancelToken,
+    Solution, SolutionError, SolutionPart, SOLUTIONS,
+};
+
+#[test]
+fn test_solvers_are_registered_by_day() {
+    let days: Vec<u8> = SOLUTIONS.iter().map(|solver| solver.day()).collect();
+    assert_eq!(days, [1, 2, 3, 4, 5, 6]);
+    assert!(find(25).is_none());
+}
+
+#[test]
+fn test_solve_parts_through_registry() {
+    let solver = find(2).unwrap();
+    let example = &solver.examples()[0];
+
+    let solved = solver
+        .solve_parts(
+            example.input,
+            &[SolutionPart::PartOne, SolutionPart::PartTwo],
+        )
+        .unwrap();
+
+    let answers: Vec<String> = solved
+        .parts
+        .into_iter()
+        .map(|part_solved| part_solved.answer.unwrap())
+        .collect();
+    assert_eq!(answers, ["8", "2286"]);
+}
+
+#[test]
+fn test_parsed_models_are_public() {
+    let games = DayTwo::parse("Game 1: 3 blue, 4 red; 1 red, 2 green").unwrap();
+    assert_eq!(games[0].index, 1);
+    assert_eq!(games[0].draws[1].green, 2);
+
+    let almanac = DayFive::parse(DayFive::EXAMPLES[0].input).unwrap();
+    assert_eq!(almanac.seeds, [79, 14, 55, 13]);
+}
+
+#[test]
+fn test_parse_errors_are_reported() {
+    let Err(SolutionError::DataParsingError(err)) = DayTwo::parse("Game 1: 3 purple") else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((err.line, err.column), (1, 9));
+}
+
+#[test]
+fn test_unwinnable_race_has_no_solution() {
+    let races = DaySix::parse("Time: 5\nDistance: 100").unwrap();
+    assert!(matches!(
+        DaySix::part_one(&races),
+        Err(SolutionError::NoPossibleSolution)
+    ));
+}
+
+#[test]
+fn test_cards_winning_past_the_table_have_no_solution() {
+    let cards = DayFour::parse("Card 1: 1 2 | 1 2").unwrap();
+    assert!(matches!(
+        DayFour::part_two(&cards),
+        Err(SolutionError::NoPossibleSolution)
+    ));
+}
+
+#[test]
+fn test_long_solves_stop_when_cancelled() {
+    let token = CancelToken::default();
+    token.cancel();
+
+    let races = DaySix::parse("Time: 4000000000\nDistance: 4000000000000000000").unwrap();
+    let solved = token.scope(|| DaySix::part_one(&races));
+    assert!(matches!(solved, Err(SolutionError::Cancelled)));
+
+    let cards = DayFour::parse(DayFour::EXAMPLES[0].input).unwrap();
+    let solved = token.scope(|| DayFour::part_two(&cards));
+    assert!(matches!(solved, Err(SolutionError::Cancelled)));
+}